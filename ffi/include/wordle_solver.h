@@ -0,0 +1,138 @@
+/*
+ * C interface to wordle-solver. See ffi/src/lib.rs for the details of each function.
+ *
+ * Build the library with `cargo build --release -p wordle-solver-ffi`, which leaves a
+ * shared library (libwordle_solver_ffi.so, .dylib or wordle_solver_ffi.dll) in
+ * target/release.
+ *
+ * Words and patterns are passed as NUL-terminated UTF-8 strings. Functions that return a
+ * word or pattern write it into a caller-provided buffer of at least WORDLE_BUFFER_LEN
+ * bytes. Patterns are read as five of C/M/W, g/y/b or the coloured squares, and written
+ * as C (right place), M (misplaced) and W (wrong).
+ */
+
+#ifndef WORDLE_SOLVER_H
+#define WORDLE_SOLVER_H
+
+/* Generated by cbindgen from ffi/src/lib.rs; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The size of the buffers words and patterns are written into: five letters and a NUL.
+#define WORDLE_BUFFER_LEN 6
+
+// The version of this interface, bumped whenever a function or type in it changes in a way
+// programs built against an older header would notice. Compare it with
+// [`wordle_abi_version`] to check the library matches the header.
+#define WORDLE_ABI_VERSION 1
+
+// A game in progress and the guesser advising on it. Opaque to C.
+typedef struct WordleSolver WordleSolver;
+
+// What became of a call: one of the `WORDLE_` statuses below.
+//
+// A plain `int`, so that any value C passes in is safe to take.
+typedef int WordleStatus;
+
+#define WORDLE_OK 0
+
+#define WORDLE_NULL_ARGUMENT 1
+
+// A pattern was not five of `C`/`M`/`W`, `g`/`y`/`b` or the coloured squares.
+#define WORDLE_BAD_PATTERN 2
+
+// A word was not in the dictionary, or for scoring, not five letters.
+#define WORDLE_NOT_A_WORD 3
+
+// The word does not use every hint so far, in a hard mode game.
+#define WORDLE_HARD_MODE 4
+
+// No word fits the feedback together with what came before.
+#define WORDLE_CONTRADICTION 5
+
+#define WORDLE_SOLVED 6
+
+// There are not that many candidates.
+#define WORDLE_OUT_OF_RANGE 7
+
+// Something went wrong inside the solver. Reset it before using it again.
+#define WORDLE_FAILED 8
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The [`WORDLE_ABI_VERSION`] the library was built with.
+uint32_t wordle_abi_version(void);
+
+// Starts a solver using `algorithm`, one of the names the command line takes for `--impl`,
+// or `native` when NULL. Returns NULL for an unknown algorithm.
+//
+// # Safety
+// `algorithm` is NULL or a NUL-terminated string.
+WordleSolver *wordle_solver_new(const char *algorithm, bool hard);
+
+// Frees a solver. Does nothing with NULL.
+//
+// # Safety
+// `solver` is NULL or came from [`wordle_solver_new`] and has not been freed.
+void wordle_solver_free(WordleSolver *solver);
+
+// Starts a new game.
+//
+// # Safety
+// `solver` is NULL or a live solver.
+WordleStatus wordle_solver_reset(WordleSolver *solver);
+
+// Records that `word` was played and got `pattern`. Nothing changes unless this returns
+// `WORDLE_OK`.
+//
+// # Safety
+// `solver` is NULL or a live solver, and `word` and `pattern` are NULL or NUL-terminated.
+WordleStatus wordle_solver_feedback(WordleSolver *solver, const char *word, const char *pattern);
+
+// Takes back the last guess. Returns whether there was one.
+//
+// # Safety
+// `solver` is NULL or a live solver.
+bool wordle_solver_undo(WordleSolver *solver);
+
+// Writes the guesser's pick for the next guess into `out`. `WORDLE_SOLVED` once the game is
+// won.
+//
+// # Safety
+// `solver` is NULL or a live solver, and `out` is NULL or has room for
+// [`WORDLE_BUFFER_LEN`] bytes.
+WordleStatus wordle_solver_suggest(WordleSolver *solver, char *out);
+
+// How many words fit every guess so far, or 0 for NULL.
+//
+// # Safety
+// `solver` is NULL or a live solver.
+size_t wordle_solver_candidate_count(const WordleSolver *solver);
+
+// Writes candidate number `index` into `out`, counting from 0 in dictionary order.
+//
+// # Safety
+// `solver` is NULL or a live solver, and `out` is NULL or has room for
+// [`WORDLE_BUFFER_LEN`] bytes.
+WordleStatus wordle_solver_candidate(const WordleSolver *solver, size_t index, char *out);
+
+// Writes the pattern `guess` gets against `answer` into `out`, as `C`/`M`/`W` letters. Any
+// five letters may be scored, words or not.
+//
+// # Safety
+// `answer` and `guess` are NULL or NUL-terminated, and `out` is NULL or has room for
+// [`WORDLE_BUFFER_LEN`] bytes.
+WordleStatus wordle_pattern(const char *answer, const char *guess, char *out);
+
+// A description of `status`, which is never freed.
+const char *wordle_status_message(WordleStatus status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WORDLE_SOLVER_H */
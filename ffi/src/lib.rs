@@ -0,0 +1,467 @@
+//! A C interface, for embedding the solver in programs that are not written in Rust.
+//!
+//! `include/wordle_solver.h` declares everything here, and is generated from it by cbindgen:
+//! after changing the interface, run `WORDLE_UPDATE_HEADER=1 cargo test -p wordle-solver-ffi`
+//! to write it again. Words and patterns go in as NUL-terminated strings. Words and patterns
+//! come back written into buffers the caller provides, of at least [`WORDLE_BUFFER_LEN`]
+//! bytes, so the only memory the caller has to give back is the solver itself, through
+//! [`wordle_solver_free`].
+//!
+//! A solver is a [`Session`]: feed it the pattern every guess got, and ask it what to guess
+//! next and which words are left.
+//!
+//! No panic unwinds into the caller: a call that panics returns [`WORDLE_FAILED`], or NULL,
+//! `false` or 0 where it does not return a status.
+
+use std::{
+    ffi::{CStr, c_char, c_int},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use wordle_solver::{
+    Correctness, Guesser, Wordle,
+    registry::{Options, Registry},
+    session::{FeedbackError, Session},
+};
+
+/// The size of the buffers words and patterns are written into: five letters and a NUL.
+pub const WORDLE_BUFFER_LEN: usize = 6;
+
+/// The version of this interface, bumped whenever a function or type in it changes in a way
+/// programs built against an older header would notice. Compare it with
+/// [`wordle_abi_version`] to check the library matches the header.
+pub const WORDLE_ABI_VERSION: u32 = 1;
+
+type NewGuesser = Box<dyn Fn() -> Box<dyn Guesser>>;
+
+/// A game in progress and the guesser advising on it. Opaque to C.
+pub struct WordleSolver(Session<Box<dyn Guesser>, NewGuesser>);
+
+/// What became of a call: one of the `WORDLE_` statuses below.
+///
+/// A plain `int`, so that any value C passes in is safe to take.
+pub type WordleStatus = c_int;
+
+pub const WORDLE_OK: WordleStatus = 0;
+pub const WORDLE_NULL_ARGUMENT: WordleStatus = 1;
+/// A pattern was not five of `C`/`M`/`W`, `g`/`y`/`b` or the coloured squares.
+pub const WORDLE_BAD_PATTERN: WordleStatus = 2;
+/// A word was not in the dictionary, or for scoring, not five letters.
+pub const WORDLE_NOT_A_WORD: WordleStatus = 3;
+/// The word does not use every hint so far, in a hard mode game.
+pub const WORDLE_HARD_MODE: WordleStatus = 4;
+/// No word fits the feedback together with what came before.
+pub const WORDLE_CONTRADICTION: WordleStatus = 5;
+pub const WORDLE_SOLVED: WordleStatus = 6;
+/// There are not that many candidates.
+pub const WORDLE_OUT_OF_RANGE: WordleStatus = 7;
+/// Something went wrong inside the solver. Reset it before using it again.
+pub const WORDLE_FAILED: WordleStatus = 8;
+
+fn status(e: FeedbackError) -> WordleStatus {
+    match e {
+        FeedbackError::NotAWord => WORDLE_NOT_A_WORD,
+        FeedbackError::HardMode => WORDLE_HARD_MODE,
+        FeedbackError::Contradiction => WORDLE_CONTRADICTION,
+        FeedbackError::Solved => WORDLE_SOLVED,
+    }
+}
+
+/// Runs `f`, or gives `failed` if it panics, so that no panic unwinds into C.
+fn guard<T>(failed: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(failed)
+}
+
+/// `s` as UTF-8, or `None` if it is NULL or not UTF-8.
+///
+/// # Safety
+/// `s` is NULL or a NUL-terminated string.
+unsafe fn text<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
+
+/// Writes `word`, five ASCII bytes, and a NUL into `out`.
+///
+/// # Safety
+/// `out` points to at least [`WORDLE_BUFFER_LEN`] writable bytes.
+unsafe fn write_word(word: &str, out: *mut c_char) {
+    debug_assert_eq!(word.len(), WORDLE_BUFFER_LEN - 1);
+    unsafe {
+        ptr::copy_nonoverlapping(word.as_ptr().cast(), out, word.len());
+        *out.add(word.len()) = 0;
+    }
+}
+
+/// The [`WORDLE_ABI_VERSION`] the library was built with.
+#[unsafe(no_mangle)]
+pub extern "C" fn wordle_abi_version() -> u32 {
+    WORDLE_ABI_VERSION
+}
+
+/// Starts a solver using `algorithm`, one of the names the command line takes for `--impl`,
+/// or `native` when NULL. Returns NULL for an unknown algorithm.
+///
+/// # Safety
+/// `algorithm` is NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_new(
+    algorithm: *const c_char,
+    hard: bool,
+) -> *mut WordleSolver {
+    guard(ptr::null_mut(), || {
+        let name = if algorithm.is_null() {
+            "native"
+        } else {
+            match unsafe { text(algorithm) } {
+                Some(name) => name,
+                None => return ptr::null_mut(),
+            }
+        };
+        let Some(&algorithm) = Registry::builtin().get(name) else {
+            return ptr::null_mut();
+        };
+        let options = Options {
+            hard_mode: hard,
+            ..Options::default()
+        };
+        let new_guesser: NewGuesser = Box::new(move || (algorithm.new)(&options));
+        let wordle = Wordle::new().with_hard_mode(hard);
+        Box::into_raw(Box::new(WordleSolver(Session::new(wordle, new_guesser))))
+    })
+}
+
+/// Frees a solver. Does nothing with NULL.
+///
+/// # Safety
+/// `solver` is NULL or came from [`wordle_solver_new`] and has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_free(solver: *mut WordleSolver) {
+    guard((), || {
+        if !solver.is_null() {
+            drop(unsafe { Box::from_raw(solver) });
+        }
+    })
+}
+
+/// Starts a new game.
+///
+/// # Safety
+/// `solver` is NULL or a live solver.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_reset(solver: *mut WordleSolver) -> WordleStatus {
+    guard(WORDLE_FAILED, || {
+        let Some(solver) = (unsafe { solver.as_mut() }) else {
+            return WORDLE_NULL_ARGUMENT;
+        };
+        solver.0.reset();
+        WORDLE_OK
+    })
+}
+
+/// Records that `word` was played and got `pattern`. Nothing changes unless this returns
+/// `WORDLE_OK`.
+///
+/// # Safety
+/// `solver` is NULL or a live solver, and `word` and `pattern` are NULL or NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_feedback(
+    solver: *mut WordleSolver,
+    word: *const c_char,
+    pattern: *const c_char,
+) -> WordleStatus {
+    guard(WORDLE_FAILED, || {
+        let Some(solver) = (unsafe { solver.as_mut() }) else {
+            return WORDLE_NULL_ARGUMENT;
+        };
+        if word.is_null() || pattern.is_null() {
+            return WORDLE_NULL_ARGUMENT;
+        }
+        let Some(mask) = unsafe { text(pattern) }.and_then(Correctness::parse_pattern) else {
+            return WORDLE_BAD_PATTERN;
+        };
+        let Some(word) = (unsafe { text(word) }) else {
+            return WORDLE_NOT_A_WORD;
+        };
+        match solver.0.feedback(&word.to_ascii_lowercase(), mask) {
+            Ok(()) => WORDLE_OK,
+            Err(e) => status(e),
+        }
+    })
+}
+
+/// Takes back the last guess. Returns whether there was one.
+///
+/// # Safety
+/// `solver` is NULL or a live solver.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_undo(solver: *mut WordleSolver) -> bool {
+    guard(false, || {
+        unsafe { solver.as_mut() }.is_some_and(|solver| solver.0.undo())
+    })
+}
+
+/// Writes the guesser's pick for the next guess into `out`. `WORDLE_SOLVED` once the game is
+/// won.
+///
+/// # Safety
+/// `solver` is NULL or a live solver, and `out` is NULL or has room for
+/// [`WORDLE_BUFFER_LEN`] bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_suggest(
+    solver: *mut WordleSolver,
+    out: *mut c_char,
+) -> WordleStatus {
+    guard(WORDLE_FAILED, || {
+        let Some(solver) = (unsafe { solver.as_mut() }) else {
+            return WORDLE_NULL_ARGUMENT;
+        };
+        if out.is_null() {
+            return WORDLE_NULL_ARGUMENT;
+        }
+        if solver.0.is_solved() {
+            return WORDLE_SOLVED;
+        }
+        match solver.0.try_suggest() {
+            Ok(guess) => {
+                unsafe { write_word(guess, out) };
+                WORDLE_OK
+            }
+            Err(_) => WORDLE_FAILED,
+        }
+    })
+}
+
+/// How many words fit every guess so far, or 0 for NULL.
+///
+/// # Safety
+/// `solver` is NULL or a live solver.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_candidate_count(solver: *const WordleSolver) -> usize {
+    guard(0, || {
+        unsafe { solver.as_ref() }.map_or(0, |solver| solver.0.candidates().len())
+    })
+}
+
+/// Writes candidate number `index` into `out`, counting from 0 in dictionary order.
+///
+/// # Safety
+/// `solver` is NULL or a live solver, and `out` is NULL or has room for
+/// [`WORDLE_BUFFER_LEN`] bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_solver_candidate(
+    solver: *const WordleSolver,
+    index: usize,
+    out: *mut c_char,
+) -> WordleStatus {
+    guard(WORDLE_FAILED, || {
+        let Some(solver) = (unsafe { solver.as_ref() }) else {
+            return WORDLE_NULL_ARGUMENT;
+        };
+        if out.is_null() {
+            return WORDLE_NULL_ARGUMENT;
+        }
+        match solver.0.candidates().remaining().get(index) {
+            Some(&(word, _)) => {
+                unsafe { write_word(word, out) };
+                WORDLE_OK
+            }
+            None => WORDLE_OUT_OF_RANGE,
+        }
+    })
+}
+
+/// Writes the pattern `guess` gets against `answer` into `out`, as `C`/`M`/`W` letters. Any
+/// five letters may be scored, words or not.
+///
+/// # Safety
+/// `answer` and `guess` are NULL or NUL-terminated, and `out` is NULL or has room for
+/// [`WORDLE_BUFFER_LEN`] bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wordle_pattern(
+    answer: *const c_char,
+    guess: *const c_char,
+    out: *mut c_char,
+) -> WordleStatus {
+    guard(WORDLE_FAILED, || {
+        if answer.is_null() || guess.is_null() || out.is_null() {
+            return WORDLE_NULL_ARGUMENT;
+        }
+        let five_letters = |word: Option<&str>| {
+            word.filter(|w| w.len() == 5 && w.bytes().all(|b| b.is_ascii_alphabetic()))
+                .map(str::to_ascii_lowercase)
+        };
+        let (Some(answer), Some(guess)) = (
+            five_letters(unsafe { text(answer) }),
+            five_letters(unsafe { text(guess) }),
+        ) else {
+            return WORDLE_NOT_A_WORD;
+        };
+        let pattern = String::from_iter(
+            Correctness::compute(&answer, &guess)
+                .iter()
+                .map(|c| c.letter()),
+        );
+        unsafe { write_word(&pattern, out) };
+        WORDLE_OK
+    })
+}
+
+/// A description of `status`, which is never freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn wordle_status_message(status: WordleStatus) -> *const c_char {
+    match status {
+        WORDLE_OK => c"ok",
+        WORDLE_NULL_ARGUMENT => c"a required argument was NULL",
+        WORDLE_BAD_PATTERN => c"not a pattern",
+        WORDLE_NOT_A_WORD => c"not in the dictionary",
+        WORDLE_HARD_MODE => c"does not use every hint, as hard mode requires",
+        WORDLE_CONTRADICTION => c"no word fits that pattern with the ones before",
+        WORDLE_SOLVED => c"the game is already solved",
+        WORDLE_OUT_OF_RANGE => c"there are not that many candidates",
+        WORDLE_FAILED => c"the solver failed; reset it before using it again",
+        _ => c"unknown status",
+    }
+    .as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn read(buffer: &[c_char; WORDLE_BUFFER_LEN]) -> &str {
+        unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn game() {
+        let mut out = [0; WORDLE_BUFFER_LEN];
+        unsafe {
+            assert!(wordle_solver_new(c"quantum".as_ptr(), false).is_null());
+            let solver = wordle_solver_new(c"cutoff".as_ptr(), false);
+            assert_eq!(wordle_solver_suggest(solver, out.as_mut_ptr()), WORDLE_OK);
+            assert_eq!(read(&out), "tares");
+            assert_eq!(
+                wordle_solver_feedback(solver, c"TARES".as_ptr(), c"WCWWW".as_ptr()),
+                WORDLE_OK
+            );
+            assert_eq!(wordle_solver_candidate_count(solver), 401);
+            assert_eq!(
+                wordle_solver_feedback(solver, c"daily".as_ptr(), c"🟩🟩🟩⬛⬛".as_ptr()),
+                WORDLE_OK
+            );
+            assert_eq!(wordle_solver_candidate_count(solver), 1);
+            assert_eq!(
+                wordle_solver_candidate(solver, 0, out.as_mut_ptr()),
+                WORDLE_OK
+            );
+            assert_eq!(read(&out), "daiko");
+            assert_eq!(
+                wordle_solver_candidate(solver, 1, out.as_mut_ptr()),
+                WORDLE_OUT_OF_RANGE
+            );
+            assert!(wordle_solver_undo(solver));
+            assert_eq!(wordle_solver_candidate_count(solver), 401);
+            assert_eq!(
+                wordle_solver_feedback(solver, c"daiko".as_ptr(), c"CCCCC".as_ptr()),
+                WORDLE_OK
+            );
+            assert_eq!(
+                wordle_solver_suggest(solver, out.as_mut_ptr()),
+                WORDLE_SOLVED
+            );
+            assert_eq!(wordle_solver_reset(solver), WORDLE_OK);
+            assert_eq!(wordle_solver_candidate_count(solver), 12947);
+            wordle_solver_free(solver);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut out = [0; WORDLE_BUFFER_LEN];
+        unsafe {
+            let solver = wordle_solver_new(ptr::null(), true);
+            assert!(!solver.is_null());
+            assert_eq!(
+                wordle_solver_feedback(solver, c"tares".as_ptr(), c"CCXCC".as_ptr()),
+                WORDLE_BAD_PATTERN
+            );
+            assert_eq!(
+                wordle_solver_feedback(solver, c"zzzzz".as_ptr(), c"WWWWW".as_ptr()),
+                WORDLE_NOT_A_WORD
+            );
+            assert_eq!(
+                wordle_solver_feedback(solver, c"tares".as_ptr(), c"CWWWW".as_ptr()),
+                WORDLE_OK
+            );
+            assert_eq!(
+                wordle_solver_feedback(solver, c"cigar".as_ptr(), c"WWWWW".as_ptr()),
+                WORDLE_HARD_MODE
+            );
+            assert_eq!(
+                wordle_solver_feedback(solver, ptr::null(), c"WWWWW".as_ptr()),
+                WORDLE_NULL_ARGUMENT
+            );
+            assert_eq!(
+                wordle_solver_suggest(ptr::null_mut(), out.as_mut_ptr()),
+                WORDLE_NULL_ARGUMENT
+            );
+            assert_eq!(wordle_solver_candidate_count(ptr::null()), 0);
+            assert!(!wordle_solver_undo(ptr::null_mut()));
+            wordle_solver_free(solver);
+            wordle_solver_free(ptr::null_mut());
+
+            assert_eq!(
+                wordle_pattern(c"cigar".as_ptr(), c"TARES".as_ptr(), out.as_mut_ptr()),
+                WORDLE_OK
+            );
+            assert_eq!(read(&out), "WMMWW");
+            assert_eq!(
+                wordle_pattern(c"cigar".as_ptr(), c"tare".as_ptr(), out.as_mut_ptr()),
+                WORDLE_NOT_A_WORD
+            );
+            let message = CStr::from_ptr(wordle_status_message(WORDLE_HARD_MODE));
+            assert_eq!(
+                message.to_str().unwrap(),
+                "does not use every hint, as hard mode requires"
+            );
+        }
+    }
+
+    #[test]
+    fn panics() {
+        assert_eq!(guard(WORDLE_OK, || panic!("caught")), WORDLE_OK);
+        assert_eq!(wordle_abi_version(), WORDLE_ABI_VERSION);
+        let message = unsafe { CStr::from_ptr(wordle_status_message(-1)) };
+        assert_eq!(message.to_str().unwrap(), "unknown status");
+    }
+
+    /// The header is what cbindgen makes of this crate. Set `WORDLE_UPDATE_HEADER` to write
+    /// it again instead of checking it.
+    #[test]
+    fn header() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let config = cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).unwrap();
+        let mut generated = Vec::new();
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{dir}/src/lib.rs"))
+            .generate()
+            .expect("cbindgen reads the crate")
+            .write(&mut generated);
+        let generated = String::from_utf8(generated).unwrap();
+        let path = format!("{dir}/include/wordle_solver.h");
+        if std::env::var_os("WORDLE_UPDATE_HEADER").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            generated,
+            "the header is out of date; run with WORDLE_UPDATE_HEADER=1 to update it"
+        );
+    }
+}
@@ -1,117 +1,52 @@
-use std::{borrow::Cow, sync::OnceLock};
+use std::borrow::Cow;
 
-use crate::{Correctness, DICTIONARY, Guess, Guesser};
+use crate::solver::{Scorer, by_frequency};
 
-static INITIAL: OnceLock<Vec<(&'static str, usize)>> = OnceLock::new();
+use super::prune::Prune;
 
-static PATTERNS: OnceLock<Vec<[Correctness; 5]>> = OnceLock::new();
-
-pub struct Cutoff {
-    remaining: Cow<'static, Vec<(&'static str, usize)>>,
-    patterns: Cow<'static, Vec<[Correctness; 5]>>,
-}
+/// Scores like [`Prune`], but only the most frequent third of the remaining words, and at
+/// least 20 of them.
+#[derive(Default)]
+pub struct Cutoff(Prune);
 
 impl Cutoff {
     pub fn new() -> Self {
-        Self {
-            remaining: Cow::Borrowed(INITIAL.get_or_init(|| {
-                let mut words = Vec::from_iter(DICTIONARY.lines().map(|line| {
-                    let (word, count) = line
-                        .split_once(' ')
-                        .expect("every line is word + space + frequency");
-                    (word, count.parse().expect("frequency must be a number"))
-                }));
-
-                words.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
-                words
-            })),
-            patterns: Cow::Borrowed(PATTERNS.get_or_init(|| Correctness::patterns().collect())),
-        }
+        Self(Prune::new())
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Candidate {
-    word: &'static str,
-    score: f64,
-}
-
-impl Guesser for Cutoff {
-    fn guess(&mut self, history: &[Guess]) -> String {
-        if let Some(last) = history.last() {
-            match self.remaining {
-                Cow::Borrowed(remaining) => {
-                    self.remaining = Cow::Owned(
-                        remaining
-                            .iter()
-                            .filter(|&&(word, _)| last.matches(word))
-                            .copied()
-                            .collect(),
-                    );
-                }
-                Cow::Owned(ref mut owned) => {
-                    owned.retain(|&(word, _)| last.matches(word));
-                }
-            }
-        }
-        if history.is_empty() {
-            return "tares".to_string();
-        }
-        assert!(!self.remaining.is_empty());
-
-        let total: usize = self.remaining.iter().map(|&(_, count)| count).sum();
-
-        let mut best: Option<Candidate> = None;
-        let mut i = 0;
-        let stop = (self.remaining.len() / 3).max(20);
-        for &(word, count) in self.remaining.iter() {
-            let mut score = 0.0;
-
-            let check_patterns = |pattern: &[Correctness; 5]| {
-                let mut pattern_count = 0;
-                for (w, count) in self.remaining.iter() {
-                    Guess {
-                        word: Cow::Borrowed(w),
-                        mask: *pattern,
-                    }
-                    .matches(word)
-                    .then(|| {
-                        pattern_count += *count;
-                    });
-                }
-                if pattern_count == 0 {
-                    return false;
-                }
-                let p = pattern_count as f64 / total as f64;
-                score -= p * p.log2();
-                true
-            };
+impl Scorer for Cutoff {
+    /// Most frequent first, so the probes are the front of the words left.
+    fn words(&self) -> &'static [(&'static str, usize)] {
+        by_frequency()
+    }
 
-            match self.patterns {
-                Cow::Borrowed(pattens) => {
-                    self.patterns =
-                        Cow::Owned(pattens.iter().copied().filter(check_patterns).collect())
-                }
-                Cow::Owned(ref mut patterns) => patterns.retain(check_patterns),
-            }
+    fn probes<'a>(&self, remaining: &'a [(&'static str, usize)]) -> &'a [(&'static str, usize)] {
+        let stop = (remaining.len() / 3).max(20);
+        &remaining[..stop.min(remaining.len())]
+    }
 
-            score += count as f64 / total as f64;
+    /// Scores with [`Prune`], whose pruned patterns carry over from one guess to the next.
+    fn memo_key(&self) -> Option<Cow<'static, str>> {
+        None
+    }
 
-            if let Some(c) = best {
-                if score > c.score {
-                    best = Some(Candidate { word: word, score });
-                }
-            } else {
-                best = Some(Candidate { word: word, score });
-            }
+    fn score(
+        &self,
+        probe: (&'static str, usize),
+        remaining: &[(&'static str, usize)],
+        total: usize,
+    ) -> f64 {
+        self.0.score(probe, remaining, total)
+    }
 
-            i += 1;
-            if i >= stop {
-                break;
-            }
-        }
-        best.expect("there should always be at least one candidate")
-            .word
-            .to_string()
+    fn score_all(
+        &mut self,
+        probes: &[(&'static str, usize)],
+        remaining: &[(&'static str, usize)],
+        total: usize,
+        threads: usize,
+    ) -> Vec<f64> {
+        self.0.score_all(probes, remaining, total, threads)
     }
 }
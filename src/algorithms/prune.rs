@@ -1,79 +1,102 @@
 use std::{borrow::Cow, sync::OnceLock};
 
-use crate::{Correctness, DICTIONARY, Guess, Guesser};
+use crate::{Correctness, Guess, parallel, solver::Scorer};
 
-static INITIAL: OnceLock<Vec<(&'static str, usize)>> = OnceLock::new();
+use super::weight::Weight;
 
 static PATTERNS: OnceLock<Vec<[Correctness; 5]>> = OnceLock::new();
 
+/// Rates probes like [`Weight`], but stops considering a pattern once some probe turns out not
+/// to produce it, for this guess and every later one.
 pub struct Prune {
-    remaining: Cow<'static, Vec<(&'static str, usize)>>,
-    patterns: Cow<'static, Vec<[Correctness; 5]>>,
+    patterns: Cow<'static, [[Correctness; 5]]>,
 }
 
 impl Prune {
     pub fn new() -> Self {
         Self {
-            remaining: Cow::Borrowed(INITIAL.get_or_init(|| {
-                Vec::from_iter(DICTIONARY.lines().map(|line| {
-                    let (word, count) = line
-                        .split_once(' ')
-                        .expect("every line is word + space + frequency");
-                    (word, count.parse().expect("frequency must be a number"))
-                }))
-            })),
             patterns: Cow::Borrowed(PATTERNS.get_or_init(|| Correctness::patterns().collect())),
         }
     }
+
+    /// Weight of every pattern each of `probes` can still produce.
+    ///
+    /// Every worker prunes its own copy of `patterns` as it walks its chunk. That copy is a
+    /// superset of what a single sequential pass would have left at the same probe, so the
+    /// counts cover every pattern the caller will ask about.
+    fn pattern_counts(
+        &self,
+        probes: &[(&'static str, usize)],
+        remaining: &[(&'static str, usize)],
+        threads: usize,
+    ) -> Vec<Vec<([Correctness; 5], usize)>> {
+        parallel::map_chunks(probes, threads, |chunk| {
+            let mut patterns = self.patterns.to_vec();
+            chunk
+                .iter()
+                .map(|&(word, _)| {
+                    let mut counts = Vec::with_capacity(patterns.len());
+                    patterns.retain(|pattern| {
+                        let mut pattern_count = 0;
+                        for (w, count) in remaining.iter() {
+                            Guess {
+                                word: Cow::Borrowed(w),
+                                mask: *pattern,
+                            }
+                            .matches(word)
+                            .then(|| {
+                                pattern_count += *count;
+                            });
+                        }
+                        counts.push((*pattern, pattern_count));
+                        pattern_count != 0
+                    });
+                    counts
+                })
+                .collect()
+        })
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Candidate {
-    word: &'static str,
-    score: f64,
+impl Default for Prune {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl Guesser for Prune {
-    fn guess(&mut self, history: &[Guess]) -> String {
-        if let Some(last) = history.last() {
-            match self.remaining {
-                Cow::Borrowed(remaining) => {
-                    self.remaining = Cow::Owned(
-                        remaining
-                            .iter()
-                            .filter(|&&(word, _)| last.matches(word))
-                            .copied()
-                            .collect(),
-                    );
-                }
-                Cow::Owned(ref mut owned) => {
-                    owned.retain(|&(word, _)| last.matches(word));
-                }
-            }
-        }
-        if history.is_empty() {
-            return "tares".to_string();
-        }
-        assert!(!self.remaining.is_empty());
+impl Scorer for Prune {
+    /// The patterns pruned while making one guess carry over to the next.
+    fn memo_key(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    /// The [`Weight`] score, over every pattern.
+    fn score(
+        &self,
+        probe: (&'static str, usize),
+        remaining: &[(&'static str, usize)],
+        total: usize,
+    ) -> f64 {
+        Weight.score(probe, remaining, total)
+    }
 
-        let total: usize = self.remaining.iter().map(|&(_, count)| count).sum();
+    fn score_all(
+        &mut self,
+        probes: &[(&'static str, usize)],
+        remaining: &[(&'static str, usize)],
+        total: usize,
+        threads: usize,
+    ) -> Vec<f64> {
+        let counts = self.pattern_counts(probes, remaining, threads);
 
-        let mut best: Option<Candidate> = None;
-        for &(word, count) in self.remaining.iter() {
+        Vec::from_iter(probes.iter().zip(&counts).map(|(&(_, count), counts)| {
             let mut score = 0.0;
+            let mut counts = counts.iter();
 
             let check_patterns = |pattern: &[Correctness; 5]| {
-                let mut pattern_count = 0;
-                for (w, count) in self.remaining.iter() {
-                    Guess {
-                        word: Cow::Borrowed(w),
-                        mask: *pattern,
-                    }
-                    .matches(word)
-                    .then(|| {
-                        pattern_count += *count;
-                    });
-                }
+                let &(_, pattern_count) = counts
+                    .find(|(p, _)| p == pattern)
+                    .expect("workers count every pattern that is still live");
                 if pattern_count == 0 {
                     return false;
                 }
@@ -90,18 +113,7 @@ impl Guesser for Prune {
                 Cow::Owned(ref mut patterns) => patterns.retain(check_patterns),
             }
 
-            score += count as f64 / total as f64;
-
-            if let Some(c) = best {
-                if score > c.score {
-                    best = Some(Candidate { word: word, score });
-                }
-            } else {
-                best = Some(Candidate { word: word, score });
-            }
-        }
-        best.expect("there should always be at least one candidate")
-            .word
-            .to_string()
+            score + count as f64 / total as f64
+        }))
     }
 }
@@ -0,0 +1,331 @@
+//! Measuring how much a guess tells you about the answer.
+//!
+//! Everything here weighs words by their dictionary frequency, the same prior the guessers use.
+
+use std::{borrow::Cow, cmp::Reverse, collections::BTreeMap, sync::OnceLock};
+
+use crate::{Correctness, Guess, GuessError, Guesser, dictionary};
+
+/// How many of the most common remaining words [`Candidates::suggestions`] tries as guesses.
+const SUGGESTION_POOL: usize = 500;
+
+static WORDS: OnceLock<Vec<(&'static str, usize)>> = OnceLock::new();
+
+/// Every dictionary word with its frequency count, in dictionary order.
+pub fn words() -> &'static [(&'static str, usize)] {
+    WORDS.get_or_init(|| {
+        Vec::from_iter(dictionary().lines().map(|line| {
+            let (word, count) = line
+                .split_once(' ')
+                .expect("every line is word + space + frequency");
+            (word, count.parse().expect("frequency must be a number"))
+        }))
+    })
+}
+
+/// The words still consistent with everything guessed so far.
+#[derive(Debug, Clone)]
+pub struct Candidates {
+    remaining: Cow<'static, [(&'static str, usize)]>,
+}
+
+impl Candidates {
+    pub fn new() -> Self {
+        Self {
+            remaining: Cow::Borrowed(words()),
+        }
+    }
+
+    pub fn remaining(&self) -> &[(&'static str, usize)] {
+        &self.remaining
+    }
+
+    pub fn len(&self) -> usize {
+        self.remaining.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_empty()
+    }
+
+    /// Total frequency of the remaining words.
+    pub fn weight(&self) -> usize {
+        self.remaining.iter().map(|&(_, count)| count).sum()
+    }
+
+    /// Drops every word `guess` rules out.
+    pub fn apply(&mut self, guess: &Guess) {
+        self.remaining = Cow::Owned(
+            self.remaining
+                .iter()
+                .filter(|&&(word, _)| guess.matches(word))
+                .copied()
+                .collect(),
+        );
+    }
+
+    /// Weight of each pattern `guess` can produce against the remaining words.
+    pub fn patterns(&self, guess: &str) -> BTreeMap<[Correctness; 5], usize> {
+        let mut patterns = BTreeMap::new();
+        for &(word, count) in self.remaining.iter() {
+            *patterns
+                .entry(Correctness::compute(word, guess))
+                .or_default() += count;
+        }
+        patterns
+    }
+
+    /// Bits of information `guess` is expected to reveal, i.e. the entropy of its patterns.
+    pub fn expected_information(&self, guess: &str) -> f64 {
+        let total = self.weight() as f64;
+        self.patterns(guess)
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                p * (1.0 / p).log2()
+            })
+            .sum()
+    }
+
+    /// Up to `k` remaining words that are expected to reveal the most information, best first.
+    ///
+    /// Only the most common words are tried as guesses while thousands remain, as scoring every
+    /// word against every other one takes seconds at the start of a game.
+    pub fn suggestions(&self, k: usize) -> Vec<(&'static str, f64)> {
+        let mut pool = self.remaining.to_vec();
+        pool.sort_by_key(|&(_, count)| Reverse(count));
+        pool.truncate(SUGGESTION_POOL);
+        let mut scored = Vec::from_iter(
+            pool.iter()
+                .map(|&(word, _)| (word, self.expected_information(word))),
+        );
+        // Stable, so equally informative words stay in order of frequency.
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(k);
+        scored
+    }
+
+    /// Bits of information it would take to get from here down to `after`.
+    pub fn information(&self, after: &Candidates) -> f64 {
+        (self.weight() as f64 / after.weight() as f64).log2()
+    }
+}
+
+/// A finished or unfinished game, guess by guess.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Analysis {
+    pub steps: Vec<Step>,
+    /// How close the guesses came to the guesser's in expected information, from 0 to 100.
+    pub skill: f64,
+    /// How the patterns that came back compared to what was likely, from 0 to 100 with 50
+    /// being an average game.
+    pub luck: f64,
+}
+
+/// One guess, measured against the candidates left at the time.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Step {
+    pub guess: String,
+    pub pattern: [Correctness; 5],
+    /// Candidates left before the guess.
+    pub remaining: usize,
+    /// Bits the guess was expected to reveal, and the bits it did.
+    pub expected: f64,
+    pub actual: f64,
+    /// What the guesser would have played instead, and the bits it was expected to reveal.
+    pub best: String,
+    pub best_expected: f64,
+    /// `expected` as a share of `best_expected`, from 0 to 1.
+    pub skill: f64,
+    /// The chance that the pattern would have revealed less than it did, counting equally
+    /// revealing patterns as half, from 0 to 1.
+    pub luck: f64,
+}
+
+/// Replays `guesses` against `answer` and asks `guesser` what it would have played at every step,
+/// or fails with the guesser.
+///
+/// Guesses after the answer was found are ignored.
+pub fn analyze(
+    answer: &str,
+    guesses: &[&str],
+    mut guesser: impl Guesser,
+) -> Result<Analysis, GuessError> {
+    let mut candidates = Candidates::new();
+    let mut history = Vec::new();
+    let mut steps = Vec::new();
+    for &guess in guesses {
+        let pattern = Correctness::compute(answer, guess);
+        let best = guesser.try_guess(&history)?;
+        let patterns = candidates.patterns(guess);
+        let expected = candidates.expected_information(guess);
+        let best_expected = candidates.expected_information(&best);
+
+        let mut after = candidates.clone();
+        let played = Guess {
+            word: Cow::Owned(guess.to_string()),
+            mask: pattern,
+        };
+        after.apply(&played);
+        let actual = candidates.information(&after);
+
+        let total = candidates.weight() as f64;
+        let luck = patterns
+            .iter()
+            .map(|(p, &count)| {
+                let bits = (total / count as f64).log2();
+                let share = count as f64 / total;
+                if *p == pattern {
+                    share / 2.0
+                } else if bits < actual {
+                    share
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+
+        steps.push(Step {
+            guess: guess.to_string(),
+            pattern,
+            remaining: candidates.len(),
+            expected,
+            actual,
+            best,
+            best_expected,
+            // Nothing was left to learn when even the best guess reveals nothing.
+            skill: if best_expected > 0.0 {
+                (expected / best_expected).min(1.0)
+            } else {
+                1.0
+            },
+            luck,
+        });
+        history.push(played);
+        candidates = after;
+        if pattern == [Correctness::Correct; 5] {
+            break;
+        }
+    }
+
+    let mean =
+        |f: fn(&Step) -> f64| 100.0 * steps.iter().map(f).sum::<f64>() / steps.len().max(1) as f64;
+    Ok(Analysis {
+        skill: mean(|step| step.skill),
+        luck: mean(|step| step.luck),
+        steps,
+    })
+}
+
+impl Default for Candidates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{Candidates, analyze};
+    use crate::{Correctness, Guess, GuessError, Guesser};
+
+    #[test]
+    fn narrowing_down() {
+        let mut candidates = Candidates::new();
+        let before = candidates.clone();
+        let expected = candidates.expected_information("tares");
+        assert!(expected > 5.0 && expected < 12.0);
+
+        candidates.apply(&Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W W W W W],
+        });
+        assert!(candidates.len() < before.len());
+        assert!(
+            candidates
+                .remaining()
+                .iter()
+                .all(|(w, _)| !w.contains(['t', 'a', 'r', 'e', 's']))
+        );
+        assert!(before.information(&candidates) > 0.0);
+    }
+
+    #[test]
+    fn certainty() {
+        let mut candidates = Candidates::new();
+        candidates.apply(&Guess {
+            word: Cow::Borrowed("cigar"),
+            mask: mask![C C C C C],
+        });
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates.expected_information("cigar"), 0.0);
+        assert_eq!(candidates.suggestions(3), [("cigar", 0.0)]);
+    }
+
+    #[test]
+    fn suggestions() {
+        let mut candidates = Candidates::new();
+        candidates.apply(&Guess {
+            word: Cow::Borrowed("tares"),
+            mask: mask![W C W W W],
+        });
+        let suggestions = candidates.suggestions(5);
+        assert_eq!(suggestions.len(), 5);
+        assert!(suggestions.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(
+            suggestions
+                .iter()
+                .all(|&(word, _)| candidates.remaining().iter().any(|&(w, _)| w == word))
+        );
+    }
+
+    #[test]
+    fn analysis() {
+        let analysis = analyze(
+            "cigar",
+            &["tares", "lingo", "cigar", "rebut"],
+            |history: &[Guess]| if history.is_empty() { "tares" } else { "cigar" }.to_string(),
+        )
+        .unwrap();
+        assert_eq!(analysis.steps.len(), 3);
+
+        let first = &analysis.steps[0];
+        assert_eq!(first.best, "tares");
+        assert_eq!(first.skill, 1.0);
+        assert_eq!(first.pattern, Correctness::compute("cigar", "tares"));
+        assert!(first.actual > 0.0 && first.luck > 0.0 && first.luck < 1.0);
+
+        let second = &analysis.steps[1];
+        assert_eq!(second.best, "cigar");
+        assert!(second.remaining < first.remaining);
+
+        let last = &analysis.steps[2];
+        assert_eq!(last.pattern, [Correctness::Correct; 5]);
+        assert!((0.0..=100.0).contains(&analysis.skill));
+        assert!((0.0..=100.0).contains(&analysis.luck));
+    }
+
+    /// Gives up on every guess.
+    struct Broken;
+
+    impl Guesser for Broken {
+        fn guess(&mut self, _: &[Guess]) -> String {
+            unreachable!("only asked to try")
+        }
+
+        fn try_guess(&mut self, _: &[Guess]) -> Result<String, GuessError> {
+            Err(GuessError("broken".to_string()))
+        }
+    }
+
+    #[test]
+    fn failing_guesser() {
+        assert_eq!(
+            analyze("cigar", &["tares"], Broken),
+            Err(GuessError("broken".to_string()))
+        );
+    }
+}
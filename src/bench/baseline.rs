@@ -0,0 +1,370 @@
+//! Recorded benchmark results to check later runs against.
+//!
+//! A baseline file has one line per game:
+//!
+//! ```text
+//! cutoff cigar 4 tares,lingo,sugar,cigar
+//! cutoff sissy X tares,...
+//! ```
+//!
+//! that is the implementation, the answer, the guess count (`X` for a failed game) and every
+//! word played. Blank lines and lines starting with `#` are ignored.
+
+use std::{cmp::Ordering, collections::BTreeMap, fmt, io, str::FromStr};
+
+use super::{
+    Game,
+    compare::{compare, score},
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    /// Recorded games by implementation, then by answer.
+    runs: BTreeMap<String, BTreeMap<String, Entry>>,
+}
+
+/// One recorded game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub guesses: Option<usize>,
+    pub words: Vec<String>,
+}
+
+impl Baseline {
+    pub fn get(&self, implementation: &str, answer: &str) -> Option<&Entry> {
+        self.runs.get(implementation)?.get(answer)
+    }
+
+    /// Whether the baseline played `answer` and every run that did won within six guesses.
+    pub fn solved(&self, answer: &str) -> bool {
+        let mut entries = self
+            .runs
+            .values()
+            .filter_map(|run| run.get(answer))
+            .peekable();
+        entries.peek().is_some() && entries.all(|entry| entry.guesses.is_some_and(|n| n <= 6))
+    }
+
+    /// Records `games`, replacing whatever was recorded for the same answers before.
+    pub fn record(&mut self, implementation: &str, games: &[Game]) {
+        let run = self.runs.entry(implementation.to_string()).or_default();
+        for game in games {
+            run.insert(
+                game.answer.to_string(),
+                Entry {
+                    guesses: game.guesses,
+                    words: game.words.clone(),
+                },
+            );
+        }
+    }
+
+    pub fn check(&self, implementation: &str, games: &[Game]) -> Check {
+        let mut check = Check::default();
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for game in games {
+            let Some(entry) = self.get(implementation, game.answer) else {
+                check.unknown.push(game.answer);
+                continue;
+            };
+            check.known += 1;
+            match compare(game.guesses, entry.guesses) {
+                Ordering::Less => {
+                    check
+                        .improvements
+                        .push((game.answer, entry.guesses, game.guesses))
+                }
+                Ordering::Greater => {
+                    check
+                        .regressions
+                        .push((game.answer, entry.guesses, game.guesses))
+                }
+                Ordering::Equal if game.words != entry.words => check.changed.push(game.answer),
+                Ordering::Equal => {}
+            }
+            // Failures are left to the lists above, so both means are over the same games.
+            if let (Some(a), Some(b)) = (entry.guesses, game.guesses) {
+                before.push(a);
+                after.push(b);
+            }
+        }
+        check.baseline_mean = before.iter().sum::<usize>() as f64 / before.len() as f64;
+        check.mean = after.iter().sum::<usize>() as f64 / after.len() as f64;
+        check
+    }
+
+    pub fn write(&self, mut w: impl io::Write) -> io::Result<()> {
+        writeln!(w, "# implementation answer guesses words")?;
+        for (implementation, run) in &self.runs {
+            for (answer, entry) in run {
+                writeln!(
+                    w,
+                    "{implementation} {answer} {} {}",
+                    score(entry.guesses),
+                    entry.words.join(",")
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut baseline = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |reason| ParseError {
+                line: i + 1,
+                reason,
+            };
+
+            let mut fields = line.split_whitespace();
+            let (Some(implementation), Some(answer), Some(guesses)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(error("expected implementation, answer and guess count"));
+            };
+            let guesses = match guesses {
+                "X" => None,
+                n => Some(
+                    n.parse()
+                        .map_err(|_| error("guess count must be a number or X"))?,
+                ),
+            };
+            let words = Vec::from_iter(
+                fields
+                    .next()
+                    .into_iter()
+                    .flat_map(|words| words.split(','))
+                    .map(str::to_string),
+            );
+            if fields.next().is_some() {
+                return Err(error("unexpected text after the guessed words"));
+            }
+
+            baseline
+                .runs
+                .entry(implementation.to_string())
+                .or_default()
+                .insert(answer.to_string(), Entry { guesses, words });
+        }
+        Ok(baseline)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// How a run measured up against its baseline.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Check {
+    /// Answers that took more guesses than recorded, with the recorded and new guess count.
+    pub regressions: Vec<(&'static str, Option<usize>, Option<usize>)>,
+    /// Answers that took fewer guesses than recorded.
+    pub improvements: Vec<(&'static str, Option<usize>, Option<usize>)>,
+    /// Answers solved in as many guesses as recorded, but with different words.
+    pub changed: Vec<&'static str>,
+    /// Answers the baseline has no record of.
+    pub unknown: Vec<&'static str>,
+    /// How many of the games the baseline has a record of.
+    pub known: usize,
+    /// Mean guesses before and now, over the games solved both times. NaN if there are none.
+    pub baseline_mean: f64,
+    pub mean: f64,
+}
+
+impl Check {
+    /// A run passes when no game and not the average got worse.
+    ///
+    /// A run the baseline knows none of the games of fails, as there is nothing to vouch for it.
+    pub fn passed(&self) -> bool {
+        self.known > 0
+            && self.regressions.is_empty()
+            && self.mean.partial_cmp(&self.baseline_mean) != Some(Ordering::Greater)
+    }
+
+    pub fn write_text(&self, mut w: impl io::Write) -> io::Result<()> {
+        for &(answer, before, after) in &self.regressions {
+            writeln!(
+                w,
+                "regressed: {answer} {} -> {}",
+                score(before),
+                score(after)
+            )?;
+        }
+        for &(answer, before, after) in &self.improvements {
+            writeln!(
+                w,
+                "improved:  {answer} {} -> {}",
+                score(before),
+                score(after)
+            )?;
+        }
+        if !self.changed.is_empty() {
+            writeln!(
+                w,
+                "{} games took as many guesses with different words: {}",
+                self.changed.len(),
+                self.changed.join(", ")
+            )?;
+        }
+        if !self.unknown.is_empty() {
+            writeln!(w, "{} games are not in the baseline", self.unknown.len())?;
+        }
+        writeln!(
+            w,
+            "average over games solved both times: {:.4} -> {:.4}\nbaseline check {}",
+            self.baseline_mean,
+            self.mean,
+            if self.passed() { "passed" } else { "FAILED" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Baseline;
+    use crate::bench::Game;
+
+    fn game(answer: &'static str, words: &[&str]) -> Game {
+        Game {
+            answer,
+            guesses: (words.last() == Some(&answer)).then_some(words.len()),
+            words: Vec::from_iter(words.iter().map(|w| w.to_string())),
+            times: Vec::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.record(
+            "cutoff",
+            &[
+                game("cigar", &["tares", "cigar"]),
+                game("sissy", &["tares", "lingo"]),
+            ],
+        );
+        baseline.record("weight", &[game("rebut", &["tares", "rebut"])]);
+
+        let mut file = Vec::new();
+        baseline.write(&mut file).unwrap();
+        let parsed: Baseline = String::from_utf8(file).unwrap().parse().unwrap();
+        assert_eq!(parsed, baseline);
+        assert!(parsed.solved("cigar"));
+        assert!(!parsed.solved("sissy"));
+        assert!(!parsed.solved("humph"));
+        assert_eq!(parsed.get("cutoff", "sissy").unwrap().guesses, None);
+        assert_eq!(parsed.get("weight", "rebut").unwrap().guesses, Some(2));
+    }
+
+    #[test]
+    fn bad_lines() {
+        assert!("cutoff cigar".parse::<Baseline>().is_err());
+        assert_eq!(
+            "# header\ncutoff cigar four tares"
+                .parse::<Baseline>()
+                .unwrap_err()
+                .to_string(),
+            "line 2: guess count must be a number or X"
+        );
+    }
+
+    #[test]
+    fn check() {
+        let mut baseline = Baseline::default();
+        baseline.record(
+            "cutoff",
+            &[
+                game("cigar", &["tares", "cigar"]),
+                game("rebut", &["tares", "lingo", "rebut"]),
+                game("sissy", &["tares", "lingo", "sissy"]),
+            ],
+        );
+
+        let same = [
+            game("cigar", &["tares", "cigar"]),
+            game("sissy", &["tares", "lingo", "sissy"]),
+        ];
+        let check = baseline.check("cutoff", &same);
+        assert!(check.passed());
+        assert!(check.changed.is_empty());
+
+        let changed = [
+            game("cigar", &["tares", "cigar"]),
+            game("rebut", &["tares", "debut", "rebut"]),
+            game("sissy", &["tares", "sissy"]),
+            game("humph", &["tares", "humph"]),
+        ];
+        let check = baseline.check("cutoff", &changed);
+        assert!(check.passed());
+        assert_eq!(check.changed, ["rebut"]);
+        assert_eq!(check.improvements, [("sissy", Some(3), Some(2))]);
+        assert_eq!(check.unknown, ["humph"]);
+
+        let worse = [
+            game("cigar", &["tares", "lingo", "cigar"]),
+            game("rebut", &["tares", "rebut"]),
+            game("sissy", &["tares", "lingo", "sissy"]),
+        ];
+        let check = baseline.check("cutoff", &worse);
+        assert!(!check.passed());
+        assert_eq!(check.regressions, [("cigar", Some(2), Some(3))]);
+
+        let check = baseline.check("weight", &same);
+        assert!(!check.passed());
+        assert_eq!(check.unknown, ["cigar", "sissy"]);
+    }
+
+    #[test]
+    fn failures() {
+        let mut baseline = Baseline::default();
+        baseline.record(
+            "cutoff",
+            &[
+                game("cigar", &["tares", "cigar"]),
+                game("sissy", &["tares", "lingo"]),
+            ],
+        );
+
+        // Solving a game that failed is an improvement, however long it took.
+        let solved = [
+            game("cigar", &["tares", "cigar"]),
+            game(
+                "sissy",
+                &["tares", "lingo", "humph", "dwelt", "boozy", "sissy"],
+            ),
+        ];
+        let check = baseline.check("cutoff", &solved);
+        assert!(check.passed());
+        assert_eq!(check.improvements, [("sissy", None, Some(6))]);
+        assert_eq!((check.baseline_mean, check.mean), (2.0, 2.0));
+        assert!(baseline.check("cutoff", &solved[1..]).passed());
+
+        // Failing a game that was solved is a regression, however the average moves.
+        let mut failed = baseline.clone();
+        failed.record("cutoff", &solved);
+        let check = failed.check("cutoff", &[game("sissy", &["tares", "lingo"])]);
+        assert!(!check.passed());
+        assert_eq!(check.regressions, [("sissy", Some(6), None)]);
+    }
+}
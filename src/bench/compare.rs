@@ -0,0 +1,270 @@
+//! Game-by-game comparison of two benchmark runs over the same answers.
+
+use std::{cmp::Ordering, io, time::Duration};
+
+use super::Game;
+use crate::json;
+
+/// How a challenger run fared against a baseline run.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Versus {
+    /// Games the challenger solved in fewer guesses, more guesses, or the same number.
+    ///
+    /// A failed game counts as worse than any solved one.
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+    /// Every answer the two disagree on, with the baseline's and the challenger's guess count.
+    pub differences: Vec<(&'static str, Option<usize>, Option<usize>)>,
+    /// Mean of challenger minus baseline guesses over games both solved.
+    pub mean_difference: f64,
+    /// Paired t statistic of those differences and its two-sided p-value.
+    ///
+    /// The p-value uses the normal approximation, which is fine for the hundreds of games a
+    /// benchmark plays but optimistic for a handful.
+    pub t: f64,
+    pub p_value: f64,
+    /// Time the challenger spent guessing divided by the time the baseline did.
+    pub speed_ratio: f64,
+}
+
+impl Versus {
+    pub fn new(baseline: &[Game], challenger: &[Game]) -> Self {
+        assert_eq!(
+            baseline.len(),
+            challenger.len(),
+            "both runs must play the same answers"
+        );
+
+        let mut wins = 0;
+        let mut losses = 0;
+        let mut ties = 0;
+        let mut differences = Vec::new();
+        let mut paired = Vec::new();
+        for (a, b) in baseline.iter().zip(challenger) {
+            assert_eq!(a.answer, b.answer, "both runs must play the same answers");
+            match compare(b.guesses, a.guesses) {
+                Ordering::Less => wins += 1,
+                Ordering::Greater => losses += 1,
+                Ordering::Equal => ties += 1,
+            }
+            if a.guesses != b.guesses {
+                differences.push((a.answer, a.guesses, b.guesses));
+            }
+            if let (Some(a), Some(b)) = (a.guesses, b.guesses) {
+                paired.push(b as f64 - a as f64);
+            }
+        }
+
+        let n = paired.len() as f64;
+        let mean_difference = paired.iter().sum::<f64>() / n;
+        let variance = paired
+            .iter()
+            .map(|d| (d - mean_difference).powi(2))
+            .sum::<f64>()
+            / (n - 1.0);
+        let t = if variance == 0.0 {
+            // Identical runs are not evidence of anything.
+            if mean_difference == 0.0 {
+                0.0
+            } else {
+                f64::INFINITY.copysign(mean_difference)
+            }
+        } else {
+            mean_difference / (variance / n).sqrt()
+        };
+        let p_value = if t.is_nan() {
+            f64::NAN
+        } else {
+            2.0 * (1.0 - normal_cdf(t.abs()))
+        };
+
+        Self {
+            wins,
+            losses,
+            ties,
+            differences,
+            mean_difference,
+            t,
+            p_value,
+            speed_ratio: guess_time(challenger).as_secs_f64() / guess_time(baseline).as_secs_f64(),
+        }
+    }
+
+    pub fn write_text(
+        &self,
+        mut w: impl io::Write,
+        baseline: &str,
+        challenger: &str,
+    ) -> io::Result<()> {
+        writeln!(
+            w,
+            "{challenger} vs {baseline}: {} better, {} worse, {} tied",
+            self.wins, self.losses, self.ties
+        )?;
+        writeln!(
+            w,
+            "  mean difference {:+.4} guesses (t = {:.2}, p = {:.4})",
+            self.mean_difference, self.t, self.p_value
+        )?;
+        writeln!(w, "  {:.2}x the guessing time", self.speed_ratio)?;
+        for &(answer, a, b) in &self.differences {
+            let verdict = match compare(b, a) {
+                Ordering::Less => "better",
+                _ => "worse",
+            };
+            writeln!(w, "  {answer}  {} -> {}  {verdict}", score(a), score(b))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_json(
+        &self,
+        mut w: impl io::Write,
+        baseline: &str,
+        challenger: &str,
+    ) -> io::Result<()> {
+        write!(
+            w,
+            "{{\"baseline\":\"{baseline}\",\"challenger\":\"{challenger}\",\"wins\":{},\"losses\":{},\"ties\":{},\"mean_difference\":{},\"t\":{},\"p_value\":{},\"speed_ratio\":{},\"differences\":[",
+            self.wins,
+            self.losses,
+            self.ties,
+            json::number(self.mean_difference),
+            json::number(self.t),
+            json::number(self.p_value),
+            json::number(self.speed_ratio)
+        )?;
+        for (i, &(answer, a, b)) in self.differences.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(
+                w,
+                "{sep}{{\"answer\":\"{answer}\",\"baseline\":{},\"challenger\":{}}}",
+                json_guesses(a),
+                json_guesses(b)
+            )?;
+        }
+        writeln!(w, "]}}")
+    }
+}
+
+/// Writes one row per answer with every run's guess count, `X` for failures.
+pub fn write_csv(mut w: impl io::Write, names: &[&str], runs: &[Vec<Game>]) -> io::Result<()> {
+    writeln!(w, "answer,{}", names.join(","))?;
+    for (i, game) in runs.first().into_iter().flatten().enumerate() {
+        write!(w, "{}", game.answer)?;
+        for run in runs {
+            write!(w, ",{}", score(run[i].guesses))?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+/// Orders guess counts from best to worst, with failures last.
+pub(super) fn compare(a: Option<usize>, b: Option<usize>) -> Ordering {
+    a.unwrap_or(usize::MAX).cmp(&b.unwrap_or(usize::MAX))
+}
+
+pub(super) fn score(guesses: Option<usize>) -> String {
+    guesses.map_or("X".to_string(), |n| n.to_string())
+}
+
+fn json_guesses(guesses: Option<usize>) -> String {
+    guesses.map_or("null".to_string(), |n| n.to_string())
+}
+
+fn guess_time(games: &[Game]) -> Duration {
+    games.iter().flat_map(|game| &game.times).sum()
+}
+
+/// Standard normal CDF, from the Abramowitz and Stegun 7.1.26 approximation of `erf`.
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Versus, normal_cdf};
+    use crate::bench::Game;
+
+    fn run(games: &[(&'static str, Option<usize>)], time: u64) -> Vec<Game> {
+        Vec::from_iter(games.iter().map(|&(answer, guesses)| Game {
+            answer,
+            guesses,
+            words: Vec::new(),
+            times: vec![Duration::from_millis(time)],
+            error: None,
+        }))
+    }
+
+    #[test]
+    fn head_to_head() {
+        let baseline = run(
+            &[
+                ("cigar", Some(3)),
+                ("rebut", Some(4)),
+                ("sissy", None),
+                ("humph", Some(5)),
+                ("awake", Some(4)),
+            ],
+            2,
+        );
+        let challenger = run(
+            &[
+                ("cigar", Some(2)),
+                ("rebut", Some(5)),
+                ("sissy", Some(6)),
+                ("humph", Some(4)),
+                ("awake", Some(6)),
+            ],
+            3,
+        );
+        let versus = Versus::new(&baseline, &challenger);
+        assert_eq!((versus.wins, versus.losses, versus.ties), (3, 2, 0));
+        assert_eq!(
+            versus.differences,
+            [
+                ("cigar", Some(3), Some(2)),
+                ("rebut", Some(4), Some(5)),
+                ("sissy", None, Some(6)),
+                ("humph", Some(5), Some(4)),
+                ("awake", Some(4), Some(6)),
+            ]
+        );
+        assert_eq!(versus.mean_difference, 0.25);
+        assert_eq!(versus.speed_ratio, 1.5);
+        assert!(versus.p_value > 0.05);
+    }
+
+    #[test]
+    fn identical_runs() {
+        let games = run(&[("cigar", Some(3)), ("rebut", Some(4))], 1);
+        let versus = Versus::new(&games, &games);
+        assert_eq!((versus.wins, versus.losses, versus.ties), (0, 0, 2));
+        assert!(versus.differences.is_empty());
+        assert_eq!(versus.t, 0.0);
+        assert!((versus.p_value - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normal() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.96) - 0.025).abs() < 1e-4);
+    }
+}
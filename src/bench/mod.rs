@@ -0,0 +1,190 @@
+//! Plays many games at once and collects their outcomes.
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Guess, GuessError, Guesser, PlayError, Wordle};
+
+pub mod baseline;
+pub mod compare;
+pub mod report;
+pub mod trace;
+pub mod tree;
+
+pub use baseline::Baseline;
+pub use compare::Versus;
+pub use report::Report;
+
+/// The outcome of one benchmark game.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Game {
+    pub answer: &'static str,
+    /// How many guesses it took, or `None` if the guesser never found the answer.
+    pub guesses: Option<usize>,
+    /// Every word the guesser played, in order.
+    pub words: Vec<String>,
+    /// How long each `Guesser::guess` call took.
+    pub times: Vec<Duration>,
+    /// Why the game ended early, if the guesser failed or broke the rules. It counts as lost.
+    pub error: Option<String>,
+}
+
+/// Records every guess, and how long it took, into `game`.
+struct Recorded<'a, G> {
+    guesser: G,
+    game: &'a mut Game,
+}
+
+impl<G: Guesser> Guesser for Recorded<'_, G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+        let start = Instant::now();
+        let guess = self.guesser.try_guess(history)?;
+        self.game.times.push(start.elapsed());
+        self.game.words.push(guess.clone());
+        Ok(guess)
+    }
+}
+
+/// Plays every answer on up to `jobs` worker threads.
+///
+/// Workers pull the next answer off a shared counter and build a fresh guesser for every game
+/// with `mk`, so guessers never cross threads. A game the guesser fails is recorded with its
+/// error, and the run goes on. `progress` is called with the number of finished games and the
+/// total after each game. The returned games are in the same order as `answers`.
+pub fn run<G, F, P>(
+    wordle: &Wordle,
+    answers: &[&'static str],
+    jobs: usize,
+    mk: F,
+    progress: P,
+) -> Vec<Game>
+where
+    G: Guesser,
+    F: Fn() -> G + Sync,
+    P: Fn(usize, usize) + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+
+    let mut games: Vec<(usize, Game)> = thread::scope(|s| {
+        let workers: Vec<_> = (0..jobs.clamp(1, answers.len().max(1)))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut played = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&answer) = answers.get(i) else {
+                            break;
+                        };
+                        let mut game = Game {
+                            answer,
+                            guesses: None,
+                            words: Vec::new(),
+                            times: Vec::new(),
+                            error: None,
+                        };
+                        let outcome = wordle.try_play(
+                            answer,
+                            Recorded {
+                                guesser: mk(),
+                                game: &mut game,
+                            },
+                        );
+                        match outcome {
+                            Ok(guesses) => game.guesses = guesses,
+                            Err(e) => {
+                                if !matches!(e, PlayError::Guesser(_)) {
+                                    // The rules turned the last word down, so it was not played.
+                                    game.words.pop();
+                                    game.times.pop();
+                                }
+                                game.error = Some(e.to_string());
+                            }
+                        }
+                        played.push((i, game));
+                        progress(done.fetch_add(1, Ordering::Relaxed) + 1, answers.len());
+                    }
+                    played
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("benchmark worker panicked"))
+            .collect()
+    });
+
+    games.sort_unstable_by_key(|&(i, _)| i);
+    games.into_iter().map(|(_, game)| game).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::{Guess, Wordle};
+
+    #[test]
+    fn keeps_answer_order() {
+        let w = Wordle::new();
+        let answers = ["apple", "arise", "cigar", "apple", "rebut"];
+        let expected = answers.map(|answer| match answer {
+            "apple" => (answer, Some(2)),
+            "arise" => (answer, Some(1)),
+            _ => (answer, None),
+        });
+        for jobs in [1, 2, 3, 8] {
+            let games = run(
+                &w,
+                &answers,
+                jobs,
+                || {
+                    |history: &[Guess]| {
+                        if history.is_empty() {
+                            "arise".to_string()
+                        } else {
+                            "apple".to_string()
+                        }
+                    }
+                },
+                |_, _| {},
+            );
+            assert_eq!(
+                Vec::from_iter(games.iter().map(|game| (game.answer, game.guesses))),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn breaking_the_rules() {
+        let wordle = Wordle::new().with_hard_mode(true);
+        let games = run(
+            &wordle,
+            &["cigar"],
+            1,
+            || {
+                |history: &[Guess]| match history.len() {
+                    0 => "tares".to_string(),
+                    // Drops the misplaced a and r.
+                    _ => "lingo".to_string(),
+                }
+            },
+            |_, _| {},
+        );
+        assert_eq!(games[0].guesses, None);
+        assert_eq!(games[0].words, ["tares"]);
+        assert_eq!(
+            games[0].error.as_deref(),
+            Some("guessed lingo, which does not use every hint, as hard mode requires")
+        );
+    }
+}
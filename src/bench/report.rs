@@ -0,0 +1,376 @@
+//! Summary statistics over a finished benchmark run.
+
+use std::{io, time::Duration};
+
+use super::Game;
+use crate::{json, memo};
+
+/// How many of the hardest solved games a report lists.
+const WORST: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Report {
+    pub games: usize,
+    /// Solved games by guess count: `histogram[0]` is one guess, ..., `histogram[6]` is 7 or more.
+    pub histogram: [usize; 7],
+    pub failures: Vec<&'static str>,
+    /// The failed games that ended in an error, with the error.
+    pub errors: Vec<(&'static str, String)>,
+    /// Mean, median and standard deviation of the guess count over solved games.
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    /// The hardest solved games, most guesses first.
+    pub worst: Vec<(&'static str, usize)>,
+    /// Wall time of the whole run.
+    pub total_time: Duration,
+    /// Median and 99th percentile time of a single `Guesser::guess` call.
+    pub guess_p50: Duration,
+    pub guess_p99: Duration,
+    /// Lookups in the [`memo`] during the run, when they were counted.
+    pub memo: Option<memo::Stats>,
+}
+
+impl Report {
+    pub fn new(games: &[Game], total_time: Duration) -> Self {
+        let mut histogram = [0; 7];
+        let mut failures = Vec::new();
+        let mut solved = Vec::new();
+        let mut errors = Vec::new();
+        for game in games {
+            if let Some(error) = &game.error {
+                errors.push((game.answer, error.clone()));
+            }
+            match game.guesses {
+                Some(n) => {
+                    histogram[n.clamp(1, 7) - 1] += 1;
+                    solved.push((game.answer, n));
+                }
+                None => failures.push(game.answer),
+            }
+        }
+
+        let mut counts = Vec::from_iter(solved.iter().map(|&(_, n)| n as f64));
+        counts.sort_unstable_by(f64::total_cmp);
+        let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+        let median = match counts.len() {
+            0 => f64::NAN,
+            n if n % 2 == 1 => counts[n / 2],
+            n => (counts[n / 2 - 1] + counts[n / 2]) / 2.0,
+        };
+        let std_dev =
+            (counts.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / counts.len() as f64).sqrt();
+
+        // Stable, so equally hard games stay in answer order.
+        solved.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        solved.truncate(WORST);
+
+        let mut times = Vec::from_iter(games.iter().flat_map(|game| game.times.iter().copied()));
+        times.sort_unstable();
+
+        Self {
+            games: games.len(),
+            histogram,
+            failures,
+            errors,
+            mean,
+            median,
+            std_dev,
+            worst: solved,
+            total_time,
+            guess_p50: percentile(&times, 50),
+            guess_p99: percentile(&times, 99),
+            memo: None,
+        }
+    }
+
+    pub fn solved(&self) -> usize {
+        self.games - self.failures.len()
+    }
+
+    /// Histogram buckets with their labels, followed by the failed games.
+    pub fn buckets(&self) -> impl Iterator<Item = (&'static str, usize)> {
+        BUCKETS
+            .into_iter()
+            .zip(self.histogram.into_iter().chain([self.failures.len()]))
+    }
+
+    pub fn write_text(&self, mut w: impl io::Write) -> io::Result<()> {
+        writeln!(
+            w,
+            "Games:    {} ({} solved, {} failed)",
+            self.games,
+            self.solved(),
+            self.failures.len()
+        )?;
+        writeln!(
+            w,
+            "Guesses:  mean {:.3}, median {:.1}, std dev {:.3}",
+            self.mean, self.median, self.std_dev
+        )?;
+        let widest = self.buckets().map(|(_, count)| count).max().unwrap_or(0);
+        for (label, count) in self.buckets() {
+            let bar = "#".repeat(count * 40 / widest.max(1));
+            writeln!(
+                w,
+                "{}",
+                format!("  {label:>2} | {count:>5} {bar}").trim_end()
+            )?;
+        }
+        writeln!(
+            w,
+            "Worst:    {}",
+            join(self.worst.iter().map(|(word, n)| format!("{word} ({n})")))
+        )?;
+        writeln!(w, "Failed:   {}", join(self.failures.iter()))?;
+        if !self.errors.is_empty() {
+            writeln!(
+                w,
+                "Errors:   {}",
+                join(
+                    self.errors
+                        .iter()
+                        .map(|(word, error)| format!("{word} ({error})"))
+                )
+            )?;
+        }
+        writeln!(
+            w,
+            "Time:     {:.2?} total, {:.2?} p50 / {:.2?} p99 per guess",
+            self.total_time, self.guess_p50, self.guess_p99
+        )?;
+        if let Some(memo) = self.memo {
+            writeln!(
+                w,
+                "Memo:     {} hits, {} misses ({:.1}% hit)",
+                memo.hits,
+                memo.misses,
+                memo.hit_rate() * 100.0
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn write_json(&self, mut w: impl io::Write) -> io::Result<()> {
+        write!(
+            w,
+            "{{\"games\":{},\"solved\":{},\"histogram\":{{",
+            self.games,
+            self.solved()
+        )?;
+        for (i, (label, count)) in self.buckets().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(w, "{sep}\"{label}\":{count}")?;
+        }
+        write!(
+            w,
+            "}},\"mean\":{},\"median\":{},\"std_dev\":{},\"worst\":[",
+            json::number(self.mean),
+            json::number(self.median),
+            json::number(self.std_dev)
+        )?;
+        for (i, (word, n)) in self.worst.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(w, "{sep}{{\"answer\":\"{word}\",\"guesses\":{n}}}")?;
+        }
+        write!(w, "],\"failures\":[")?;
+        for (i, word) in self.failures.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(w, "{sep}\"{word}\"")?;
+        }
+        write!(w, "]")?;
+        if !self.errors.is_empty() {
+            write!(w, ",\"errors\":[")?;
+            for (i, (word, error)) in self.errors.iter().enumerate() {
+                let sep = if i == 0 { "" } else { "," };
+                write!(
+                    w,
+                    "{sep}{{\"answer\":\"{word}\",\"error\":{}}}",
+                    json::string(error)
+                )?;
+            }
+            write!(w, "]")?;
+        }
+        write!(
+            w,
+            ",\"total_ms\":{},\"guess_p50_us\":{},\"guess_p99_us\":{}",
+            self.total_time.as_millis(),
+            self.guess_p50.as_micros(),
+            self.guess_p99.as_micros()
+        )?;
+        if let Some(memo) = self.memo {
+            write!(
+                w,
+                ",\"memo\":{{\"hits\":{},\"misses\":{}}}",
+                memo.hits, memo.misses
+            )?;
+        }
+        writeln!(w, "}}")
+    }
+
+    /// Writes the report as `metric,value` rows.
+    pub fn write_csv(&self, mut w: impl io::Write) -> io::Result<()> {
+        writeln!(w, "metric,value")?;
+        writeln!(w, "games,{}", self.games)?;
+        writeln!(w, "solved,{}", self.solved())?;
+        for (label, count) in self.buckets() {
+            writeln!(w, "guesses_{label},{count}")?;
+        }
+        writeln!(w, "mean,{}", self.mean)?;
+        writeln!(w, "median,{}", self.median)?;
+        writeln!(w, "std_dev,{}", self.std_dev)?;
+        writeln!(
+            w,
+            "worst,{}",
+            self.worst
+                .iter()
+                .map(|(word, n)| format!("{word}:{n}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )?;
+        writeln!(w, "failures,{}", self.failures.join(" "))?;
+        if !self.errors.is_empty() {
+            writeln!(w, "errors,{}", self.errors.len())?;
+        }
+        writeln!(w, "total_ms,{}", self.total_time.as_millis())?;
+        writeln!(w, "guess_p50_us,{}", self.guess_p50.as_micros())?;
+        writeln!(w, "guess_p99_us,{}", self.guess_p99.as_micros())?;
+        if let Some(memo) = self.memo {
+            writeln!(w, "memo_hits,{}", memo.hits)?;
+            writeln!(w, "memo_misses,{}", memo.misses)?;
+        }
+        Ok(())
+    }
+}
+
+/// Histogram labels, followed by the label for failed games.
+const BUCKETS: [&str; 8] = ["1", "2", "3", "4", "5", "6", "7+", "X"];
+
+/// Nearest-rank percentile of sorted `times`.
+fn percentile(times: &[Duration], p: usize) -> Duration {
+    if times.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p * times.len()).div_ceil(100).max(1);
+    times[rank - 1]
+}
+
+fn join(items: impl Iterator<Item = impl ToString>) -> String {
+    let items = Vec::from_iter(items.map(|item| item.to_string()));
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Report, percentile};
+    use crate::{bench::Game, memo};
+
+    fn game(answer: &'static str, guesses: Option<usize>) -> Game {
+        Game {
+            answer,
+            guesses,
+            words: Vec::new(),
+            times: vec![Duration::from_millis(1); guesses.unwrap_or(6)],
+            error: None,
+        }
+    }
+
+    #[test]
+    fn statistics() {
+        let games = [
+            game("blush", Some(2)),
+            game("cigar", Some(4)),
+            game("sissy", Some(5)),
+            game("rebut", Some(4)),
+            game("naval", None),
+            game("humph", Some(5)),
+            game("awake", Some(4)),
+            game("focal", Some(7)),
+            game("evade", Some(9)),
+        ];
+        let report = Report::new(&games, Duration::from_secs(1));
+        assert_eq!(report.games, 9);
+        assert_eq!(report.solved(), 8);
+        assert_eq!(report.histogram, [0, 1, 0, 3, 2, 0, 2]);
+        assert_eq!(report.failures, ["naval"]);
+        assert_eq!(report.mean, 5.0);
+        assert_eq!(report.median, 4.5);
+        assert_eq!(report.std_dev, 2.0);
+        assert_eq!(
+            report.worst,
+            [
+                ("evade", 9),
+                ("focal", 7),
+                ("sissy", 5),
+                ("humph", 5),
+                ("cigar", 4),
+                ("rebut", 4),
+                ("awake", 4),
+                ("blush", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut errored = game("rebut", None);
+        errored.error = Some("bot sh: no guess within 500ms".to_string());
+        let report = Report::new(&[game("cigar", Some(3)), errored], Duration::ZERO);
+        assert_eq!(report.failures, ["rebut"]);
+        assert_eq!(
+            report.errors,
+            [("rebut", "bot sh: no guess within 500ms".to_string())]
+        );
+        let mut text = Vec::new();
+        report.write_text(&mut text).unwrap();
+        assert!(
+            String::from_utf8(text)
+                .unwrap()
+                .contains("\nErrors:   rebut (bot sh: no guess within 500ms)\n")
+        );
+        let mut json = Vec::new();
+        report.write_json(&mut json).unwrap();
+        assert!(String::from_utf8(json).unwrap().contains(
+            r#""failures":["rebut"],"errors":[{"answer":"rebut","error":"bot sh: no guess within 500ms"}],"#
+        ));
+    }
+
+    #[test]
+    fn memo() {
+        let mut report = Report::new(&[game("cigar", Some(3))], Duration::ZERO);
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        assert!(!String::from_utf8(csv).unwrap().contains("memo"));
+
+        report.memo = Some(memo::Stats { hits: 3, misses: 1 });
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        assert!(
+            String::from_utf8(csv)
+                .unwrap()
+                .ends_with("memo_hits,3\nmemo_misses,1\n")
+        );
+        let mut text = Vec::new();
+        report.write_text(&mut text).unwrap();
+        assert!(
+            String::from_utf8(text)
+                .unwrap()
+                .ends_with("Memo:     3 hits, 1 misses (75.0% hit)\n")
+        );
+    }
+
+    #[test]
+    fn percentiles() {
+        let times = Vec::from_iter((1..=200).map(Duration::from_micros));
+        assert_eq!(percentile(&times, 50), Duration::from_micros(100));
+        assert_eq!(percentile(&times, 99), Duration::from_micros(198));
+        assert_eq!(percentile(&[], 99), Duration::ZERO);
+    }
+}
@@ -0,0 +1,103 @@
+//! A guess-by-guess account of a finished game, for debugging strategies.
+
+use std::{borrow::Cow, io, time::Duration};
+
+use super::Game;
+use crate::{Correctness, Guess, analysis::Candidates};
+
+/// What one guess of a game did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub word: String,
+    pub mask: [Correctness; 5],
+    /// Candidate words before and after the guess.
+    pub before: usize,
+    pub after: usize,
+    /// Bits the guess was expected to reveal, and the bits it did reveal.
+    pub expected: f64,
+    pub actual: f64,
+    pub time: Duration,
+}
+
+/// Replays `game` against the dictionary to see what every guess did.
+pub fn trace(game: &Game) -> Vec<Step> {
+    let mut candidates = Candidates::new();
+    let mut steps = Vec::new();
+    for (word, &time) in game.words.iter().zip(&game.times) {
+        let mask = Correctness::compute(game.answer, word);
+        let expected = candidates.expected_information(word);
+        let before = candidates.clone();
+        candidates.apply(&Guess {
+            word: Cow::Borrowed(word),
+            mask,
+        });
+        steps.push(Step {
+            word: word.clone(),
+            mask,
+            before: before.len(),
+            after: candidates.len(),
+            expected,
+            actual: before.information(&candidates),
+            time,
+        });
+    }
+    steps
+}
+
+/// Writes the trace of `game`, showing patterns as share-grid squares when `emoji` is set.
+pub fn write_trace(mut w: impl io::Write, game: &Game, emoji: bool) -> io::Result<()> {
+    match game.guesses {
+        Some(n) => writeln!(w, "{}: solved in {n}", game.answer)?,
+        None => writeln!(w, "{}: failed", game.answer)?,
+    }
+    for (i, step) in trace(game).iter().enumerate() {
+        let mask = String::from_iter(
+            step.mask
+                .iter()
+                .map(|&c| if emoji { c.emoji() } else { c.letter() }),
+        );
+        writeln!(
+            w,
+            "  {:>2}. {} {mask}  {:>5} -> {:<5}  expected {:.2} bits, got {:.2}  {:.2?}",
+            i + 1,
+            step.word,
+            step.before,
+            step.after,
+            step.expected,
+            step.actual,
+            step.time
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::trace;
+    use crate::bench::Game;
+
+    #[test]
+    fn replay() {
+        let game = Game {
+            answer: "cigar",
+            guesses: Some(3),
+            words: vec!["tares".into(), "lingo".into(), "cigar".into()],
+            times: vec![Duration::ZERO; 3],
+            error: None,
+        };
+        let steps = trace(&game);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].mask, mask![W M M W W]);
+        assert_eq!(steps[1].before, steps[0].after);
+        assert!(steps[0].after < steps[0].before);
+        assert_eq!(steps[2].mask, mask![C C C C C]);
+        assert_eq!(steps[2].after, 1);
+        assert!(
+            steps
+                .iter()
+                .all(|step| step.actual >= 0.0 && step.expected >= 0.0)
+        );
+    }
+}
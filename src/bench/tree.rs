@@ -0,0 +1,171 @@
+//! The decision tree a guesser follows across many answers.
+//!
+//! A guesser that only looks at the history makes the same guess whenever the same patterns
+//! came back, so the games it plays share prefixes and together form a tree: each node is a
+//! guess, and each pattern that guess can get leads to the next node.
+
+use std::{collections::BTreeMap, io};
+
+use super::Game;
+use crate::Correctness;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tree {
+    pub root: Option<Node>,
+    /// Answers whose game disagreed with the tree built from earlier games, which happens for
+    /// guessers that are not deterministic. They are left out of the tree.
+    pub inconsistent: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub guess: String,
+    /// How many answers reach this node.
+    pub answers: usize,
+    /// Whether a game ended here because `guess` was the answer.
+    pub solved: bool,
+    pub children: BTreeMap<[Correctness; 5], Node>,
+}
+
+impl Node {
+    fn new(guess: &str) -> Self {
+        Self {
+            guess: guess.to_string(),
+            answers: 0,
+            solved: false,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// The longest path from here to a leaf, counting this node.
+    pub fn depth(&self) -> usize {
+        1 + self.children.values().map(Node::depth).max().unwrap_or(0)
+    }
+}
+
+impl Tree {
+    pub fn new(games: &[Game]) -> Self {
+        let mut tree = Self::default();
+        for game in games {
+            if !tree.fits(game) {
+                tree.inconsistent.push(game.answer);
+                continue;
+            }
+            let Some(first) = game.words.first() else {
+                continue;
+            };
+            let mut node = tree.root.get_or_insert_with(|| Node::new(first));
+            for (i, word) in game.words.iter().enumerate() {
+                node.answers += 1;
+                let mask = Correctness::compute(game.answer, word);
+                if mask == [Correctness::Correct; 5] {
+                    node.solved = true;
+                    break;
+                }
+                let Some(next) = game.words.get(i + 1) else {
+                    break;
+                };
+                node = node.children.entry(mask).or_insert_with(|| Node::new(next));
+            }
+        }
+        tree
+    }
+
+    /// Whether `game` made the same guesses as the tree wherever they overlap.
+    fn fits(&self, game: &Game) -> bool {
+        let mut node = self.root.as_ref();
+        for word in &game.words {
+            let Some(n) = node else {
+                return true;
+            };
+            if n.guess != *word {
+                return false;
+            }
+            node = n.children.get(&Correctness::compute(game.answer, word));
+        }
+        true
+    }
+
+    pub fn write_text(&self, mut w: impl io::Write) -> io::Result<()> {
+        fn write_node(
+            w: &mut impl io::Write,
+            node: &Node,
+            pattern: Option<&[Correctness; 5]>,
+            depth: usize,
+        ) -> io::Result<()> {
+            let indent = "  ".repeat(depth);
+            let pattern = pattern.map_or(String::new(), |p| {
+                String::from_iter(p.iter().map(|c| c.letter()).chain([' ']))
+            });
+            let solved = if node.solved { " *" } else { "" };
+            writeln!(
+                w,
+                "{indent}{pattern}{} {}{solved}",
+                node.guess, node.answers
+            )?;
+            for (pattern, child) in &node.children {
+                write_node(w, child, Some(pattern), depth + 1)?;
+            }
+            Ok(())
+        }
+
+        if let Some(root) = &self.root {
+            write_node(&mut w, root, None, 0)?;
+            writeln!(
+                w,
+                "{} answers, at most {} guesses deep",
+                root.answers,
+                root.depth()
+            )?;
+        }
+        if !self.inconsistent.is_empty() {
+            writeln!(
+                w,
+                "left out {} games that strayed from the tree: {}",
+                self.inconsistent.len(),
+                self.inconsistent.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tree;
+    use crate::bench::Game;
+
+    fn game(answer: &'static str, words: &[&str]) -> Game {
+        Game {
+            answer,
+            guesses: (words.last() == Some(&answer)).then_some(words.len()),
+            words: Vec::from_iter(words.iter().map(|w| w.to_string())),
+            times: Vec::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn shared_prefixes() {
+        let tree = Tree::new(&[
+            game("cigar", &["tares", "lingo", "cigar"]),
+            game("tares", &["tares"]),
+            game("humph", &["tares", "which", "humph"]),
+            game("sissy", &["raise", "sissy"]),
+            game("rebut", &["tares", "rebut"]),
+        ]);
+        assert_eq!(tree.inconsistent, ["sissy"]);
+
+        let root = tree.root.unwrap();
+        assert_eq!(root.guess, "tares");
+        assert_eq!(root.answers, 4);
+        assert!(root.solved);
+        assert_eq!(root.children.len(), 3);
+        assert_eq!(root.depth(), 3);
+        let lingo = &root.children[&mask![W M M W W]];
+        assert_eq!((lingo.guess.as_str(), lingo.answers), ("lingo", 1));
+        assert!(!lingo.solved);
+        assert!(lingo.children[&mask![W C W M W]].solved);
+        assert!(root.children[&mask![M W M M W]].solved);
+    }
+}
@@ -0,0 +1,402 @@
+//! Opening books: the first guesses of a game, worked out once instead of in every game.
+//!
+//! A guesser's opener never changes, and its second guess depends only on the pattern the
+//! opener got, so there are only a couple of hundred second guesses it can ever play. Scoring
+//! one is the slowest step of most games, as nearly every word is still possible. A [`Book`]
+//! holds them, and optionally the third guesses, and [`Book::guesser`] plays from it until
+//! the game leaves the book.
+//!
+//! A book file starts with the guesser it was made from and its opener, followed by one line
+//! per reply with the patterns so far joined by `:` and the word to play next:
+//!
+//! ```text
+//! book cutoff tares
+//! WMMWW daily
+//! WMMWW:CCWWW fiery
+//! ```
+//!
+//! Blank lines and lines starting with `#` are ignored.
+
+use std::{borrow::Cow, collections::BTreeMap, fmt, io, str::FromStr};
+
+use crate::{Correctness, Guess, GuessError, Guesser, Wordle, analysis, parallel};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Book {
+    /// The guesser the book was made from.
+    name: String,
+    opener: String,
+    /// The guess to play next, by the patterns of the guesses so far.
+    replies: BTreeMap<Vec<[Correctness; 5]>, String>,
+}
+
+impl Book {
+    /// Works out the first `depth` guesses the guessers `new_guesser` builds play against
+    /// every dictionary word, scoring on up to `threads` workers, or fails with the first of
+    /// them that fails. `name` records which guesser it was.
+    ///
+    /// Every reply is asked of a new guesser shown the whole game up to it at once, so the
+    /// guesser has to narrow its words by every guess it is shown, as
+    /// [`Solver`](crate::solver::Solver) does.
+    pub fn build<G, F>(
+        name: &str,
+        new_guesser: F,
+        depth: usize,
+        threads: usize,
+    ) -> Result<Self, GuessError>
+    where
+        G: Guesser,
+        F: Fn() -> G + Sync,
+    {
+        let mut book = Self {
+            name: name.to_string(),
+            opener: new_guesser().try_guess(&[])?,
+            replies: BTreeMap::new(),
+        };
+        // Games the book has the next guess of, with the words each of them leaves.
+        let mut games: Vec<(Vec<Guess<'static>>, Vec<&'static str>)> = vec![(
+            Vec::new(),
+            Vec::from_iter(analysis::words().iter().map(|&(word, _)| word)),
+        )];
+        for _ in 1..depth {
+            let mut next = Vec::new();
+            for (game, remaining) in &games {
+                let word = book.next(game).expect("every game so far is in the book");
+                let mut patterns = BTreeMap::<_, Vec<_>>::new();
+                for &answer in remaining {
+                    let mask = Correctness::compute(answer, word);
+                    if mask != [Correctness::Correct; 5] {
+                        patterns.entry(mask).or_default().push(answer);
+                    }
+                }
+                for (mask, remaining) in patterns {
+                    let mut game = game.clone();
+                    game.push(Guess {
+                        word: Cow::Owned(word.to_string()),
+                        mask,
+                    });
+                    next.push((game, remaining));
+                }
+            }
+            let replies = parallel::map(&next, threads, |(game, _)| new_guesser().try_guess(game));
+            for ((game, _), reply) in next.iter().zip(replies) {
+                book.replies
+                    .insert(Vec::from_iter(game.iter().map(|g| g.mask)), reply?);
+            }
+            games = next;
+        }
+        Ok(book)
+    }
+
+    /// The guesser the book was made from.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn opener(&self) -> &str {
+        &self.opener
+    }
+
+    /// How many replies the book holds, not counting the opener.
+    pub fn len(&self) -> usize {
+        self.replies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replies.is_empty()
+    }
+
+    /// The book's next guess after `history`, if every guess so far was the book's own and it
+    /// goes that deep.
+    pub fn next(&self, history: &[Guess]) -> Option<&str> {
+        let mut next = Some(self.opener.as_str());
+        for (n, guess) in history.iter().enumerate() {
+            if next != Some(&*guess.word) {
+                return None;
+            }
+            let patterns = Vec::from_iter(history[..=n].iter().map(|g| g.mask));
+            next = self.replies.get(&patterns).map(String::as_str);
+        }
+        next
+    }
+
+    /// Plays from the book while it can, and asks `guesser` from then on.
+    ///
+    /// `guesser` is first asked once the game leaves the book, and then shown everything
+    /// played so far at once, so it has to narrow by every guess it is shown. A guesser that
+    /// learns from scoring one guess to use on the next, like
+    /// [`Prune`](crate::algorithms::prune::Prune), may go on differently than it would have
+    /// after working out the book's guesses itself.
+    pub fn guesser<G: Guesser>(&self, guesser: G) -> Booked<'_, G> {
+        Booked {
+            book: self,
+            guesser,
+        }
+    }
+
+    pub fn write(&self, mut w: impl io::Write) -> io::Result<()> {
+        writeln!(w, "book {} {}", self.name, self.opener)?;
+        for (patterns, reply) in &self.replies {
+            let patterns = Vec::from_iter(
+                patterns
+                    .iter()
+                    .map(|mask| String::from_iter(mask.iter().map(|c| c.letter()))),
+            );
+            writeln!(w, "{} {reply}", patterns.join(":"))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Book {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wordle = Wordle::new();
+        let mut book: Option<Self> = None;
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |reason| ParseError {
+                line: i + 1,
+                reason,
+            };
+
+            let fields = Vec::from_iter(line.split_whitespace());
+            let Some(book) = &mut book else {
+                let &["book", name, opener] = fields.as_slice() else {
+                    return Err(error("expected `book`, the guesser and its opener"));
+                };
+                if !wordle.is_valid(opener) {
+                    return Err(error("the opener is not in the dictionary"));
+                }
+                book = Some(Self {
+                    name: name.to_string(),
+                    opener: opener.to_string(),
+                    replies: BTreeMap::new(),
+                });
+                continue;
+            };
+            let &[patterns, reply] = fields.as_slice() else {
+                return Err(error("expected patterns and a reply"));
+            };
+            if !wordle.is_valid(reply) {
+                return Err(error("the reply is not in the dictionary"));
+            }
+            let patterns = patterns
+                .split(':')
+                .map(Correctness::parse_pattern)
+                .collect::<Option<Vec<_>>>()
+                .ok_or(error("patterns must be five of C, M or W each"))?;
+            if book.replies.insert(patterns, reply.to_string()).is_some() {
+                return Err(error("the same patterns are in the book twice"));
+            }
+        }
+        book.ok_or(ParseError {
+            line: 0,
+            reason: "the book is empty",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            0 => write!(f, "{}", self.reason),
+            line => write!(f, "line {line}: {}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A guesser that plays from a [`Book`] before it thinks for itself.
+pub struct Booked<'a, G> {
+    book: &'a Book,
+    guesser: G,
+}
+
+impl<G: Guesser> Guesser for Booked<'_, G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        match self.book.next(history) {
+            Some(word) => word.to_string(),
+            None => self.guesser.guess(history),
+        }
+    }
+
+    fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+        match self.book.next(history) {
+            Some(word) => Ok(word.to_string()),
+            None => self.guesser.try_guess(history),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Book;
+    use crate::{
+        Correctness, Guess, GuessError, Guesser, Wordle, bench,
+        solver::{Scorer, Solver, by_frequency},
+    };
+
+    /// Guesses the most frequent word left.
+    struct Frequent;
+
+    impl Scorer for Frequent {
+        fn words(&self) -> &'static [(&'static str, usize)] {
+            by_frequency()
+        }
+
+        fn probes<'a>(
+            &self,
+            remaining: &'a [(&'static str, usize)],
+        ) -> &'a [(&'static str, usize)] {
+            &remaining[..1]
+        }
+
+        fn score(&self, _: (&'static str, usize), _: &[(&'static str, usize)], _: usize) -> f64 {
+            0.0
+        }
+    }
+
+    fn frequent() -> Solver<Frequent> {
+        Solver::new(Frequent)
+    }
+
+    /// Guesses like [`Frequent`] without keeping any state, which is quick enough to build
+    /// deep books with.
+    fn most_frequent(history: &[Guess]) -> String {
+        let (word, _) = by_frequency()
+            .iter()
+            .find(|&&(word, _)| history.iter().all(|g| g.matches(word)))
+            .expect("the answer is left");
+        word.to_string()
+    }
+
+    #[test]
+    fn builds() {
+        let book = Book::build("frequent", frequent, 2, 2).unwrap();
+        assert_eq!(book.opener(), "tares");
+        assert!(book.len() > 100);
+
+        let answers = ["cigar", "rebut", "sissy", "tares"];
+        let wordle = Wordle::new();
+        let with_book = bench::run(&wordle, &answers, 1, || book.guesser(frequent()), |_, _| {});
+        let without = bench::run(&wordle, &answers, 1, frequent, |_, _| {});
+        for (with_book, without) in with_book.iter().zip(&without) {
+            assert_eq!(with_book.words, without.words, "{}", with_book.answer);
+        }
+    }
+
+    #[test]
+    fn deep() {
+        let shallow = Book::build("frequent", || most_frequent, 2, 1).unwrap();
+        let book = Book::build("frequent", || most_frequent, 3, 2).unwrap();
+        assert_eq!(book.opener(), by_frequency()[0].0);
+        assert!(book.len() > shallow.len() * 2);
+
+        let answers = ["cigar", "rebut", "sissy", "tares"];
+        let wordle = Wordle::new();
+        let with_book = bench::run(
+            &wordle,
+            &answers,
+            1,
+            || book.guesser(most_frequent),
+            |_, _| {},
+        );
+        let without = bench::run(&wordle, &answers, 1, || most_frequent, |_, _| {});
+        for (with_book, without) in with_book.iter().zip(&without) {
+            assert_eq!(with_book.words, without.words, "{}", with_book.answer);
+        }
+    }
+
+    #[test]
+    fn next() {
+        let book = Book::build("frequent", frequent, 2, 1).unwrap();
+        let guess = |word: &str, answer| Guess {
+            word: word.to_string().into(),
+            mask: Correctness::compute(answer, word),
+        };
+        assert_eq!(book.next(&[]), Some("tares"));
+        let reply = book.next(&[guess("tares", "cigar")]).unwrap();
+        // The book stops after the reply, and for any game that played something else.
+        assert_eq!(
+            book.next(&[guess("tares", "cigar"), guess(reply, "cigar")]),
+            None
+        );
+        assert_eq!(book.next(&[guess("crane", "cigar")]), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let book = Book::build("frequent", frequent, 2, 1).unwrap();
+        let mut file = Vec::new();
+        book.write(&mut file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        assert!(file.starts_with("book frequent tares\n"));
+        assert_eq!(file.parse::<Book>(), Ok(book));
+
+        assert!("".parse::<Book>().is_err());
+        assert_eq!(
+            "book a tares\nWWWWX cigar"
+                .parse::<Book>()
+                .unwrap_err()
+                .to_string(),
+            "line 2: patterns must be five of C, M or W each"
+        );
+        assert!(
+            "book a tares\nWWWWW cigar\nWWWWW rebut"
+                .parse::<Book>()
+                .is_err()
+        );
+        for (file, error) in [
+            (
+                "book a zzzzz",
+                "line 1: the opener is not in the dictionary",
+            ),
+            (
+                "book a tares\n\nWWWWW cigars",
+                "line 3: the reply is not in the dictionary",
+            ),
+        ] {
+            assert_eq!(file.parse::<Book>().unwrap_err().to_string(), error);
+        }
+    }
+
+    /// Opens with tares, and fails after that.
+    struct Failing;
+
+    impl Guesser for Failing {
+        fn guess(&mut self, _: &[Guess]) -> String {
+            unreachable!("only asked to try")
+        }
+
+        fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+            match history {
+                [] => Ok("tares".to_string()),
+                _ => Err(GuessError("no more".to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn failing_guesser() {
+        assert_eq!(
+            Book::build("failing", || Failing, 2, 1),
+            Err(GuessError("no more".to_string()))
+        );
+        assert_eq!(
+            Book::build("failing", || Failing, 1, 1).map(|book| book.opener().to_string()),
+            Ok("tares".to_string())
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use std::{io::Write, process::ExitCode};
+use wordle_solver::registry::Registry;
+
+pub fn run(registry: &Registry) -> ExitCode {
+    let width = registry
+        .iter()
+        .map(|a| a.name)
+        .chain(registry.aliases().map(|(alias, _)| alias))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let mut stdout = std::io::stdout().lock();
+    for algorithm in registry.iter() {
+        let capabilities = algorithm.capabilities;
+        let flags = Vec::from_iter(
+            [
+                (capabilities.hard_mode, "hard"),
+                (capabilities.ranking, "ranks"),
+                (capabilities.multi_board, "multi"),
+            ]
+            .into_iter()
+            .map(|(has, flag)| if has { flag } else { "-" }),
+        );
+        writeln!(
+            stdout,
+            "{:width$}  {:16}  {}",
+            algorithm.name,
+            flags.join(" "),
+            algorithm.description
+        )
+        .expect("Failed to write to stdout");
+    }
+    for (alias, name) in registry.aliases() {
+        writeln!(stdout, "{alias:width$}  plays as {name}").expect("Failed to write to stdout");
+    }
+    ExitCode::SUCCESS
+}
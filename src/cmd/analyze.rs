@@ -0,0 +1,84 @@
+use clap::Args as ClapArgs;
+use std::{io::Write, process::ExitCode};
+use wordle_solver::analysis::analyze;
+
+use super::{DayArgs, fail};
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The words played, in order
+    #[arg(required = true, value_name = "GUESS")]
+    guesses: Vec<String>,
+
+    /// The answer of the game
+    #[arg(short, long, required_unless_present_any = ["date", "today"], conflicts_with_all = ["date", "today"])]
+    answer: Option<String>,
+
+    #[command(flatten)]
+    day: DayArgs,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let answer = match (&args.answer, args.day.puzzle()) {
+        (Some(answer), _) => answer.to_ascii_lowercase(),
+        (None, Ok(Some((_, answer)))) => answer.to_string(),
+        (None, Ok(None)) => unreachable!("clap requires an answer or a day"),
+        (None, Err(e)) => return fail(e),
+    };
+    let guesses = Vec::from_iter(args.guesses.iter().map(|g| g.to_ascii_lowercase()));
+    let wordle = global.wordle();
+    if let Some(word) = std::iter::once(&answer)
+        .chain(&guesses)
+        .find(|word| !wordle.is_valid(word))
+    {
+        return fail(format!("{word} is not in the dictionary"));
+    }
+
+    let guesses = Vec::from_iter(guesses.iter().map(String::as_str));
+    let analysis = match analyze(&answer, &guesses, global.guesser()) {
+        Ok(analysis) => analysis,
+        Err(e) => return fail(format!("cannot analyze the game: {e}")),
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        " #  guess  pattern      left  expected  actual  luck   best   expected  skill"
+    )
+    .expect("Failed to write to stdout");
+    for (i, step) in analysis.steps.iter().enumerate() {
+        writeln!(
+            stdout,
+            "{:>2}  {}  {}  {:>5}  {:>8.2}  {:>6.2}  {:>4.0}   {}  {:>8.2}  {:>5.0}",
+            i + 1,
+            step.guess,
+            String::from_iter(step.pattern.iter().map(|c| c.emoji())),
+            step.remaining,
+            step.expected,
+            step.actual,
+            step.luck * 100.0,
+            step.best,
+            step.best_expected,
+            step.skill * 100.0
+        )
+        .expect("Failed to write to stdout");
+    }
+    if guesses.len() > analysis.steps.len() {
+        writeln!(
+            stdout,
+            "ignored {} guesses after the answer was found",
+            guesses.len() - analysis.steps.len()
+        )
+        .expect("Failed to write to stdout");
+    }
+    writeln!(
+        stdout,
+        "skill {:.0}/100, luck {:.0}/100 against {}",
+        analysis.skill,
+        analysis.luck,
+        global.name()
+    )
+    .expect("Failed to write to stdout");
+    ExitCode::SUCCESS
+}
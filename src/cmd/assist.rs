@@ -0,0 +1,209 @@
+use clap::Args as ClapArgs;
+use std::{io::Write, process::ExitCode};
+use wordle_solver::{
+    Correctness,
+    endgame::{self, NoPlan},
+    session::{FeedbackError, Session},
+};
+
+use super::{DayArgs, fail};
+use crate::Global;
+
+const HELP: &str = "\
+Enter the pattern the game showed for the suggested word, as CMWWW, gybbb or share-grid squares.
+  WORD PATTERN  you played WORD instead, and the game showed PATTERN
+  undo          take back the last guess
+  list          show every word that still fits
+  quit          stop";
+
+const HELP_KNOWN_ANSWER: &str = "\
+Press enter to play the suggested word, or type the word you want to play.
+  undo          take back the last guess
+  list          show every word that still fits
+  quit          stop";
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// How many of the most informative words to suggest alongside the guesser's pick
+    #[arg(short = 'k', long, default_value_t = 5)]
+    suggestions: usize,
+
+    // Knowing the day's answer, the patterns can be worked out instead of asked for.
+    #[command(flatten)]
+    day: DayArgs,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let mut session = Session::new(global.wordle(), || global.guesser());
+
+    let answer = match args.day.puzzle() {
+        Ok(day) => day.map(|(_, answer)| answer),
+        Err(e) => return fail(e),
+    };
+    let help = if answer.is_some() {
+        HELP_KNOWN_ANSWER
+    } else {
+        HELP
+    };
+
+    let mut stdout = std::io::stdout();
+    writeln!(stdout, "{help}").expect("Failed to write to stdout");
+    loop {
+        let played = session.history().len();
+        if session.is_solved() {
+            writeln!(
+                stdout,
+                "Solved in {played} {}!",
+                if played == 1 { "guess" } else { "guesses" }
+            )
+            .expect("Failed to write to stdout");
+            return ExitCode::SUCCESS;
+        }
+        if played == 6 {
+            writeln!(
+                stdout,
+                "Out of guesses; {} words still fit. Type `undo` to fix a pattern, or `list` to see them.",
+                session.candidates().len()
+            )
+            .expect("Failed to write to stdout");
+        } else if let Err(e) = session.try_suggest() {
+            writeln!(
+                stdout,
+                "\n{} words fit. No suggestion: {e}. Type the word you played and its pattern.",
+                session.candidates().len()
+            )
+            .expect("Failed to write to stdout");
+        } else {
+            // Already worked out just above.
+            let guess = session.suggest().to_string();
+            let left = session.candidates();
+            if left.len() == 1 {
+                writeln!(
+                    stdout,
+                    "\nOnly one word fits. Guess {}: {guess}",
+                    played + 1
+                )
+                .expect("Failed to write to stdout");
+            } else {
+                let others = Vec::from_iter(
+                    left.suggestions(args.suggestions)
+                        .into_iter()
+                        .map(|(word, bits)| format!("{word} ({bits:.2} bits)")),
+                );
+                writeln!(
+                    stdout,
+                    "\n{} words fit. Guess {}: {guess}",
+                    left.len(),
+                    played + 1
+                )
+                .expect("Failed to write to stdout");
+                if !others.is_empty() {
+                    writeln!(stdout, "Also worth a try: {}", others.join(", "))
+                        .expect("Failed to write to stdout");
+                }
+                let turns = 6 - played;
+                // The same search the guesser makes with --endgame, so only then.
+                if global
+                    .endgame
+                    .is_some_and(|words| left.len() <= words.into())
+                    && endgame::solve(left.remaining(), turns, global.hard)
+                        == Err(NoPlan::OutOfTurns)
+                {
+                    writeln!(
+                        stdout,
+                        "No way of guessing is sure to find the answer in the {turns} guesses left."
+                    )
+                    .expect("Failed to write to stdout");
+                }
+            }
+        }
+        write!(stdout, "> ").expect("Failed to write to stdout");
+        stdout.flush().expect("Failed to write to stdout");
+
+        let mut line = String::new();
+        if std::io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read line")
+            == 0
+        {
+            return ExitCode::SUCCESS;
+        }
+        let line = line.trim();
+
+        match line {
+            "" if answer.is_none() => continue,
+            "quit" | "exit" => return ExitCode::SUCCESS,
+            "help" | "?" => writeln!(stdout, "{help}").expect("Failed to write to stdout"),
+            "list" => {
+                let mut words = Vec::from_iter(
+                    session
+                        .candidates()
+                        .remaining()
+                        .iter()
+                        .map(|&(word, _)| word),
+                );
+                words.sort_unstable();
+                for row in words.chunks(10) {
+                    writeln!(stdout, "{}", row.join(" ")).expect("Failed to write to stdout");
+                }
+            }
+            "undo" => {
+                if !session.undo() {
+                    writeln!(stdout, "Nothing to undo.").expect("Failed to write to stdout");
+                }
+            }
+            _ if played == 6 => {
+                writeln!(stdout, "No guesses left.").expect("Failed to write to stdout");
+            }
+            _ => {
+                let is_word = |w: &str| w.len() == 5 && w.bytes().all(|b| b.is_ascii_alphabetic());
+                let (word, pattern) = match line.split_once(char::is_whitespace) {
+                    Some((word, pattern)) if is_word(word) => {
+                        (word.to_ascii_lowercase(), Some(pattern))
+                    }
+                    // Knowing the answer, a lone word is a guess to score rather than a pattern.
+                    None if answer.is_some() && is_word(line) => (line.to_ascii_lowercase(), None),
+                    _ => {
+                        let suggested = match session.try_suggest() {
+                            Ok(guess) => guess.to_string(),
+                            Err(e) => {
+                                writeln!(stdout, "No suggestion to play: {e}.")
+                                    .expect("Failed to write to stdout");
+                                continue;
+                            }
+                        };
+                        (suggested, (!line.is_empty()).then_some(line))
+                    }
+                };
+                let mask = match (pattern, answer) {
+                    (None, Some(answer)) => Correctness::compute(answer, &word),
+                    (pattern, _) => {
+                        let pattern = pattern.unwrap_or_default();
+                        let Some(mask) = Correctness::parse_pattern(pattern) else {
+                            writeln!(
+                                stdout,
+                                "Could not read {pattern:?} as a pattern; type `help` for the formats."
+                            )
+                            .expect("Failed to write to stdout");
+                            continue;
+                        };
+                        mask
+                    }
+                };
+                match session.feedback(&word, mask) {
+                    Ok(()) => writeln!(
+                        stdout,
+                        "{word} {}",
+                        String::from_iter(mask.iter().map(|c| c.emoji()))
+                    ),
+                    Err(FeedbackError::Contradiction) => writeln!(
+                        stdout,
+                        "No word fits that; check the pattern and try again."
+                    ),
+                    Err(e) => writeln!(stdout, "{word}: {e}."),
+                }
+                .expect("Failed to write to stdout");
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use clap::Args as ClapArgs;
+use std::{path::PathBuf, process::ExitCode, time::Instant};
+use wordle_solver::{
+    bench::{self, Baseline, Report, trace},
+    memo,
+};
+
+use super::{Format, GameArgs, fail, progress, read_baseline};
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(flatten)]
+    games: GameArgs,
+
+    /// How to print the benchmark report
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Record every game's guesses for this implementation into a baseline file
+    #[arg(long, value_name = "FILE")]
+    write_baseline: Option<PathBuf>,
+
+    /// Fail if any game or the average does worse than recorded in a baseline file
+    #[arg(long, value_name = "FILE")]
+    check_baseline: Option<PathBuf>,
+
+    /// Print every guess of every game with its pattern, candidates left, information and time
+    #[arg(long)]
+    trace: bool,
+
+    /// Show traced patterns as share-grid squares
+    #[arg(long, requires = "trace")]
+    emoji: bool,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let answers = match args.games.select() {
+        Ok(answers) => answers,
+        Err(e) => return fail(e),
+    };
+    let name = global.name();
+    let lookups = memo::stats();
+    let start = Instant::now();
+    let games = bench::run(
+        &global.wordle(),
+        &answers,
+        args.games.jobs(),
+        || global.guesser(),
+        progress(""),
+    );
+
+    let mut report = Report::new(&games, start.elapsed());
+    let lookups = memo::stats().since(lookups);
+    // Guessers that never look in the memo have nothing to report.
+    report.memo = (lookups != memo::Stats::default()).then_some(lookups);
+
+    let mut stdout = std::io::stdout().lock();
+    match args.format {
+        Format::Text => {
+            for game in &games {
+                let answer = game.answer;
+                if args.trace {
+                    trace::write_trace(&mut stdout, game, args.emoji)
+                        .expect("Failed to write to stdout");
+                } else if let Some(time) = game.guesses {
+                    println!("Solved {answer} in {time} guesses");
+                } else if let Some(error) = &game.error {
+                    println!("Failed to solve {answer}: {error}");
+                } else {
+                    println!("Failed to solve {answer}");
+                }
+            }
+            report.write_text(stdout)
+        }
+        format => {
+            // Keep machine-readable output clean by tracing to stderr instead.
+            if args.trace {
+                let stderr = std::io::stderr();
+                for game in &games {
+                    trace::write_trace(&stderr, game, args.emoji)
+                        .expect("Failed to write to stderr");
+                }
+            }
+            match format {
+                Format::Json => report.write_json(stdout),
+                _ => report.write_csv(stdout),
+            }
+        }
+    }
+    .expect("Failed to write to stdout");
+
+    if let Some(path) = &args.write_baseline {
+        let mut recorded = if path.exists() {
+            match read_baseline(path) {
+                Ok(recorded) => recorded,
+                Err(e) => return fail(e),
+            }
+        } else {
+            Baseline::default()
+        };
+        recorded.record(&name, &games);
+        let mut file = Vec::new();
+        recorded.write(&mut file).expect("writing to memory");
+        if let Err(e) = std::fs::write(path, file) {
+            return fail(format!("cannot write {}: {e}", path.display()));
+        }
+    }
+
+    if let Some(path) = &args.check_baseline {
+        let recorded = match read_baseline(path) {
+            Ok(recorded) => recorded,
+            Err(e) => return fail(e),
+        };
+        let check = recorded.check(&name, &games);
+        check
+            .write_text(std::io::stderr())
+            .expect("Failed to write to stderr");
+        if !check.passed() {
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,50 @@
+use clap::Args as ClapArgs;
+use std::{io::Write, path::PathBuf, process::ExitCode, time::Instant};
+use wordle_solver::book::Book;
+
+use super::fail;
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// How many guesses deep the book goes, counting the opener
+    #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=3))]
+    depth: u8,
+
+    /// Write the book to this file [default: standard output]
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let start = Instant::now();
+    let book = match Book::build(
+        &global.name(),
+        || global.guesser(),
+        args.depth.into(),
+        global.threads,
+    ) {
+        Ok(book) => book,
+        Err(e) => return fail(format!("cannot build the book: {e}")),
+    };
+    eprintln!(
+        "{} replies to {} in {:.2?}",
+        book.len(),
+        book.opener(),
+        start.elapsed()
+    );
+
+    let mut file = Vec::new();
+    book.write(&mut file).expect("writing to memory");
+    match &args.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, file) {
+                return fail(format!("cannot write {}: {e}", path.display()));
+            }
+        }
+        None => std::io::stdout()
+            .write_all(&file)
+            .expect("Failed to write to stdout"),
+    }
+    ExitCode::SUCCESS
+}
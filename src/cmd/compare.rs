@@ -0,0 +1,85 @@
+use clap::Args as ClapArgs;
+use std::{io::Write, process::ExitCode, time::Duration};
+use wordle_solver::{
+    bench::{self, Report, Versus, compare},
+    registry::Algorithm,
+};
+
+use super::{Format, GameArgs, fail, progress};
+use crate::{Global, algorithm};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Implementations to run; the first one is the baseline the others are compared to
+    #[arg(value_parser = algorithm(), required = true, num_args = 2..)]
+    implementations: Vec<&'static Algorithm>,
+
+    #[command(flatten)]
+    games: GameArgs,
+
+    /// How to print the comparison
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    if global.bot.is_some() {
+        return fail("compare plays the built-in guessers it is given, which --bot replaces");
+    }
+    if global.book.is_some() {
+        return fail("a --book is made for a single guesser, and compare plays several");
+    }
+    let answers = match args.games.select() {
+        Ok(answers) => answers,
+        Err(e) => return fail(e),
+    };
+    let wordle = global.wordle();
+    let names = Vec::from_iter(args.implementations.iter().map(|&i| global.name_of(i)));
+    let names = Vec::from_iter(names.iter().map(String::as_str));
+    let runs = Vec::from_iter(args.implementations.iter().zip(&names).map(
+        |(&implementation, name)| {
+            bench::run(
+                &wordle,
+                &answers,
+                args.games.jobs(),
+                || (implementation.new)(&global.options()),
+                progress(name),
+            )
+        },
+    ));
+
+    let mut stdout = std::io::stdout().lock();
+    match args.format {
+        Format::Text => {
+            for (name, games) in names.iter().zip(&runs) {
+                let report = Report::new(games, Duration::ZERO);
+                let time: Duration = games.iter().flat_map(|game| &game.times).sum();
+                writeln!(
+                    stdout,
+                    "{name}: {} of {} solved, mean {:.3} guesses, {time:.2?} guessing",
+                    report.solved(),
+                    report.games,
+                    report.mean
+                )
+                .expect("Failed to write to stdout");
+            }
+            for (name, games) in names.iter().zip(&runs).skip(1) {
+                writeln!(stdout).expect("Failed to write to stdout");
+                Versus::new(&runs[0], games)
+                    .write_text(&mut stdout, names[0], name)
+                    .expect("Failed to write to stdout");
+            }
+        }
+        Format::Json => {
+            for (name, games) in names.iter().zip(&runs).skip(1) {
+                Versus::new(&runs[0], games)
+                    .write_json(&mut stdout, names[0], name)
+                    .expect("Failed to write to stdout");
+            }
+        }
+        Format::Csv => {
+            compare::write_csv(stdout, &names, &runs).expect("Failed to write to stdout")
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,67 @@
+use clap::Args as ClapArgs;
+use std::{io::Write, path::PathBuf, process::ExitCode};
+use wordle_solver::{
+    analysis, daily,
+    share::{self, Share},
+};
+
+use super::fail;
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// File with the shared texts [default: read standard input]
+    file: Option<PathBuf>,
+
+    /// Consider every dictionary word, not just the words on the answer list
+    #[arg(long)]
+    any_word: bool,
+
+    /// How many of the likeliest answers to show
+    #[arg(short = 'n', long, default_value_t = 10)]
+    top: usize,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let text = match &args.file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display())),
+        None => std::io::read_to_string(std::io::stdin())
+            .map_err(|e| format!("cannot read standard input: {e}")),
+    };
+    let shares = match text.map(|text| Share::parse_all(&text).map_err(|e| format!("{e}"))) {
+        Ok(Ok(shares)) => shares,
+        Ok(Err(e)) | Err(e) => return fail(e),
+    };
+    let mut numbers = Vec::from_iter(shares.iter().filter_map(|share| share.number));
+    numbers.sort_unstable();
+    numbers.dedup();
+    if numbers.len() > 1 {
+        return fail(format!(
+            "the shares are from different puzzles: {}",
+            Vec::from_iter(numbers.iter().map(|n| n.to_string())).join(", ")
+        ));
+    }
+
+    let answers = if args.any_word {
+        Vec::from_iter(analysis::words().iter().map(|&(word, _)| word))
+    } else {
+        daily::answers().to_vec()
+    };
+    let posterior = share::posterior(&shares, &answers, global.threads);
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        "{} shares with {} rows leave {} possible answers",
+        shares.len(),
+        shares.iter().map(|share| share.rows.len()).sum::<usize>(),
+        posterior.len()
+    )
+    .expect("Failed to write to stdout");
+    for (i, (answer, p)) in posterior.iter().take(args.top).enumerate() {
+        writeln!(stdout, "{:>3}. {answer}  {:>6.2}%", i + 1, p * 100.0)
+            .expect("Failed to write to stdout");
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,198 @@
+//! The subcommands, and the options and helpers several of them share.
+
+use clap::{Args, ValueEnum};
+use std::{
+    io::IsTerminal,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use wordle_solver::{
+    bench::Baseline,
+    daily::{self, Date},
+    rng::Rng,
+};
+
+pub mod algorithms;
+pub mod analyze;
+pub mod assist;
+pub mod bench;
+pub mod book;
+pub mod compare;
+pub mod deduce;
+pub mod openers;
+pub mod play;
+pub mod protocol;
+pub mod serve;
+pub mod share;
+pub mod tree;
+
+/// Which answers to play and how many at once. The filters apply in the order they are listed.
+#[derive(Args)]
+pub struct GameArgs {
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Play this word; may be repeated, and replaces the answer list
+    #[arg(short, long = "word", value_name = "WORD", conflicts_with_all = ["date", "today"])]
+    words: Vec<String>,
+
+    /// Read answers from a file instead of the built-in list
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["date", "today"])]
+    answers_file: Option<PathBuf>,
+
+    /// Only play answers at these 0-based positions, like 100..200
+    #[arg(long, value_name = "A..B", value_parser = parse_range)]
+    range: Option<Range<usize>>,
+
+    /// Skip answers every run in this baseline file solved within six guesses
+    #[arg(long, value_name = "FILE")]
+    skip_solved_by: Option<PathBuf>,
+
+    /// Play a random subset of this many answers
+    #[arg(long, value_name = "N")]
+    sample: Option<usize>,
+
+    /// Seed for --sample
+    #[arg(long, default_value_t = 0, requires = "sample")]
+    seed: u64,
+
+    /// Play at most this many answers
+    #[arg(short, long)]
+    max: Option<usize>,
+
+    /// Games to play at once [default: available cores]
+    #[arg(short, long)]
+    jobs: Option<usize>,
+}
+
+impl GameArgs {
+    pub fn select(&self) -> Result<Vec<&'static str>, String> {
+        let mut answers = if let Some((_, answer)) = self.day.puzzle()? {
+            vec![answer]
+        } else if !self.words.is_empty() {
+            Vec::from_iter(self.words.iter().map(|word| &*word.clone().leak()))
+        } else if let Some(path) = &self.answers_file {
+            let list = std::fs::read_to_string(path)
+                .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+            Vec::from_iter(list.leak().split_whitespace())
+        } else {
+            daily::answers().to_vec()
+        };
+        if let Some(bad) = answers
+            .iter()
+            .find(|word| word.len() != 5 || !word.bytes().all(|b| b.is_ascii_lowercase()))
+        {
+            return Err(format!("{bad:?} is not a five letter lowercase word"));
+        }
+
+        if let Some(range) = &self.range {
+            if range.start > answers.len() {
+                return Err(format!(
+                    "range starts at {}, past the {} answers",
+                    range.start,
+                    answers.len()
+                ));
+            }
+            answers = answers[range.start..range.end.min(answers.len())].to_vec();
+        }
+        if let Some(path) = &self.skip_solved_by {
+            let baseline = read_baseline(path)?;
+            answers.retain(|answer| !baseline.solved(answer));
+        }
+        if let Some(n) = self.sample {
+            answers = Rng::new(self.seed).sample(&answers, n);
+        }
+        answers.truncate(self.max.unwrap_or(usize::MAX));
+        Ok(answers)
+    }
+
+    pub fn jobs(&self) -> usize {
+        self.jobs.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+    }
+}
+
+fn parse_range(s: &str) -> Result<Range<usize>, String> {
+    let (start, end) = s
+        .split_once("..")
+        .ok_or_else(|| "expected a range like 100..200".to_string())?;
+    let bound = |n: &str| n.parse::<usize>().map_err(|e| format!("{n:?}: {e}"));
+    let start = if start.is_empty() { 0 } else { bound(start)? };
+    let end = if end.is_empty() {
+        usize::MAX
+    } else {
+        bound(end)?
+    };
+    if start > end {
+        return Err("the range ends before it starts".to_string());
+    }
+    Ok(start..end)
+}
+
+/// Picks the puzzle of a given day.
+#[derive(Args)]
+pub struct DayArgs {
+    /// Use the puzzle of this day
+    #[arg(long, value_name = "YYYY-MM-DD", conflicts_with = "today")]
+    date: Option<Date>,
+
+    /// Use today's puzzle, going by UTC
+    #[arg(long)]
+    today: bool,
+}
+
+impl DayArgs {
+    /// The number and answer of the chosen day's puzzle, if a day was chosen.
+    pub fn puzzle(&self) -> Result<Option<(usize, &'static str)>, String> {
+        let Some(date) = self.date.or(self.today.then(Date::today)) else {
+            return Ok(None);
+        };
+        match daily::number(date) {
+            Some(n) => Ok(Some((n, daily::answers()[n]))),
+            None => Err(format!(
+                "there is no puzzle for {date}; the list runs from {} for {} days",
+                daily::EPOCH,
+                daily::answers().len()
+            )),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+pub fn read_baseline(path: &Path) -> Result<Baseline, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?
+        .parse()
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+pub fn fail(message: impl std::fmt::Display) -> ExitCode {
+    eprintln!("error: {message}");
+    ExitCode::from(2)
+}
+
+/// Reports benchmark progress on stderr when someone is watching.
+pub fn progress(label: &str) -> impl Fn(usize, usize) + Sync + '_ {
+    let show = std::io::stderr().is_terminal();
+    move |done, total| {
+        if show {
+            eprint!(
+                "\r{label}{}{done}/{total} games played",
+                if label.is_empty() { "" } else { ": " }
+            );
+            if done == total {
+                eprintln!();
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use clap::{Args as ClapArgs, ValueEnum};
+use std::{process::ExitCode, time::Duration};
+use wordle_solver::{
+    analysis,
+    bench::{self, Report},
+    openers::{self, Opener},
+    registry::Options,
+};
+
+use super::{Format, GameArgs, fail, progress};
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// What to rank the openers by
+    #[arg(long, value_enum, default_value_t = Rank::Bits)]
+    by: Rank,
+
+    /// How many of the best openers to list, or 0 for every word
+    #[arg(short = 'n', long, default_value_t = 20)]
+    top: usize,
+
+    /// Also benchmark the guesser opening with each of the best N, on the answers picked below,
+    /// listing the average over solved games and how many it failed
+    #[arg(long, value_name = "N")]
+    simulate: Option<usize>,
+
+    #[command(flatten)]
+    games: GameArgs,
+
+    /// How to print the leaderboard
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+enum Rank {
+    /// Most expected information first
+    Bits,
+    /// Fewest words expected to be left first
+    Expected,
+    /// Smallest largest pattern first
+    Worst,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    if global.bot.is_some() && args.simulate.is_some() {
+        return fail("--simulate plays the built-in guessers, which --bot replaces");
+    }
+    let answers = match args.simulate.map(|_| args.games.select()).transpose() {
+        Ok(answers) => answers,
+        Err(e) => return fail(e),
+    };
+
+    let mut openers = openers::score(analysis::words(), global.threads);
+    // Stable, so equally good openers stay in dictionary order.
+    match args.by {
+        Rank::Bits => openers.sort_by(|a, b| b.bits.total_cmp(&a.bits)),
+        Rank::Expected => openers.sort_by(|a, b| a.expected.total_cmp(&b.expected)),
+        Rank::Worst => openers.sort_by_key(|opener| opener.worst),
+    }
+    if args.top > 0 {
+        openers.truncate(args.top);
+    }
+
+    if let (Some(n), Some(answers)) = (args.simulate, answers) {
+        let wordle = global.wordle();
+        for opener in openers.iter_mut().take(n) {
+            let options = Options {
+                opener: Some(opener.word.to_string()),
+                ..global.options()
+            };
+            let games = bench::run(
+                &wordle,
+                &answers,
+                args.games.jobs(),
+                || (global.implementation.new)(&options),
+                progress(opener.word),
+            );
+            let report = Report::new(&games, Duration::ZERO);
+            opener.average = Some(report.mean);
+            opener.failed = Some(report.failures.len());
+        }
+    }
+
+    write(&openers, args.format).expect("Failed to write to stdout");
+    ExitCode::SUCCESS
+}
+
+fn write(openers: &[Opener], format: Format) -> std::io::Result<()> {
+    let stdout = std::io::stdout().lock();
+    match format {
+        Format::Text => openers::write_text(openers, stdout),
+        Format::Json => openers::write_json(openers, stdout),
+        Format::Csv => openers::write_csv(openers, stdout),
+    }
+}
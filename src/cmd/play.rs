@@ -0,0 +1,178 @@
+use clap::Args as ClapArgs;
+use std::{
+    borrow::Cow,
+    io::{IsTerminal, Write},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use wordle_solver::{Correctness, Guess, daily, rng::Rng};
+
+use super::{DayArgs, fail};
+use crate::Global;
+
+const KEYBOARD: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Play the answer at this 0-based position in the answer list, which is also its puzzle number
+    #[arg(long, value_name = "N", conflicts_with_all = ["date", "today", "seed"])]
+    index: Option<usize>,
+
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Seed for picking a random answer [default: the current time]
+    #[arg(long, conflicts_with_all = ["date", "today"])]
+    seed: Option<u64>,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let answers = daily::answers();
+    let day = match args.day.puzzle() {
+        Ok(day) => day,
+        Err(e) => return fail(e),
+    };
+    let number = if let Some(n) = args.index {
+        if n >= answers.len() {
+            return fail(format!(
+                "there are only {} puzzles, numbered from 0",
+                answers.len()
+            ));
+        }
+        n
+    } else if let Some((n, _)) = day {
+        n
+    } else {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the clock is past 1970");
+        Rng::new(args.seed.unwrap_or(now.as_nanos() as u64)).below(answers.len())
+    };
+    let answer = answers[number];
+
+    let wordle = global.wordle();
+    let color = std::io::stdout().is_terminal();
+    let mut history: Vec<Guess<'static>> = Vec::new();
+    let mut keys = [None; 26];
+
+    println!(
+        "Guess the five letter word in six tries{}.",
+        if wordle.is_hard_mode() {
+            ", using every hint you get"
+        } else {
+            ""
+        }
+    );
+    while history.len() < 6 && history.last().is_none_or(|g| g.word != answer) {
+        print!("{}> ", history.len() + 1);
+        std::io::stdout()
+            .flush()
+            .expect("Failed to write to stdout");
+        let mut line = String::new();
+        if std::io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read line")
+            == 0
+        {
+            println!("\nThe word was {answer}.");
+            return ExitCode::SUCCESS;
+        }
+        let word = line.trim().to_ascii_lowercase();
+
+        if word.len() != 5 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+            println!("Guesses are five letter words.");
+            continue;
+        }
+        if word != answer && !wordle.is_valid(&word) {
+            println!("{word} is not in the dictionary.");
+            continue;
+        }
+        if word != answer && !wordle.allows(&history, &word) {
+            println!("Hard mode: {word} has to use every hint so far.");
+            continue;
+        }
+
+        let mask = Correctness::compute(answer, &word);
+        for (b, c) in word.bytes().zip(mask) {
+            let key = &mut keys[(b - b'a') as usize];
+            // Correctness orders best first, and a key shows the best it has been.
+            *key = Some(key.map_or(c, |k: Correctness| k.min(c)));
+        }
+        println!("{}", tiles(&word, &mask, color));
+        println!("{}", keyboard(&keys, color));
+        history.push(Guess {
+            word: Cow::Owned(word),
+            mask,
+        });
+    }
+
+    let solved = history.last().is_some_and(|g| g.word == answer);
+    if !solved {
+        println!("The word was {answer}.");
+    }
+    println!(
+        "\nWordle {number} {}/6{}",
+        if solved {
+            history.len().to_string()
+        } else {
+            "X".to_string()
+        },
+        if wordle.is_hard_mode() { "*" } else { "" }
+    );
+    for guess in &history {
+        println!(
+            "{}",
+            String::from_iter(guess.mask.iter().map(|c| c.emoji()))
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// A guess as coloured tiles, or followed by its share-grid row where colours are unavailable.
+fn tiles(word: &str, mask: &[Correctness; 5], color: bool) -> String {
+    if color {
+        String::from_iter(
+            word.chars()
+                .zip(mask)
+                .map(|(w, &c)| paint(&format!(" {} ", w.to_ascii_uppercase()), Some(c))),
+        )
+    } else {
+        format!(
+            "{word} {}",
+            String::from_iter(mask.iter().map(|c| c.emoji()))
+        )
+    }
+}
+
+/// What every letter is known to be, laid out like a keyboard.
+fn keyboard(keys: &[Option<Correctness>; 26], color: bool) -> String {
+    let state = |c: char| keys[(c as u8 - b'a') as usize];
+    if color {
+        Vec::from_iter(KEYBOARD.iter().enumerate().map(|(indent, row)| {
+            " ".repeat(indent)
+                + &String::from_iter(row.chars().map(|c| paint(&format!("{c} "), state(c))))
+        }))
+        .join("\n")
+    } else {
+        let letters = |want: Option<Correctness>| {
+            String::from_iter(KEYBOARD.concat().chars().filter(|&c| state(c) == want))
+        };
+        format!(
+            "green: {}  yellow: {}  grey: {}  unused: {}",
+            letters(Some(Correctness::Correct)),
+            letters(Some(Correctness::Misplaced)),
+            letters(Some(Correctness::Wrong)),
+            letters(None)
+        )
+    }
+}
+
+fn paint(text: &str, state: Option<Correctness>) -> String {
+    let code = match state {
+        Some(Correctness::Correct) => "30;42",
+        Some(Correctness::Misplaced) => "30;43",
+        Some(Correctness::Wrong) => "37;100",
+        None => return text.to_string(),
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
@@ -0,0 +1,17 @@
+use std::process::ExitCode;
+use wordle_solver::{protocol, session::Session};
+
+use super::fail;
+use crate::Global;
+
+pub fn run(global: &Global) -> ExitCode {
+    let mut session = Session::new(global.wordle(), || global.guesser());
+    match protocol::serve(
+        &mut session,
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+    ) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => fail(format!("protocol stopped: {e}")),
+    }
+}
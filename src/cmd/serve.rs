@@ -0,0 +1,43 @@
+use clap::Args as ClapArgs;
+use std::{
+    net::TcpListener,
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use wordle_solver::{server::Server, session::Session};
+
+use super::fail;
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The address to listen on
+    #[arg(long, value_name = "HOST:PORT", default_value = "127.0.0.1:8080")]
+    addr: String,
+
+    /// Seed for the answers of hosted games, instead of the current time
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let listener = match TcpListener::bind(&args.addr) {
+        Ok(listener) => listener,
+        Err(e) => return fail(format!("cannot listen on {}: {e}", args.addr)),
+    };
+    let seed = args.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the clock is past 1970")
+            .as_nanos() as u64
+    });
+    let mut server = Server::new(Session::new(global.wordle(), || global.guesser()), seed);
+    eprintln!(
+        "serving {} on http://{}",
+        global.name(),
+        listener
+            .local_addr()
+            .map_or(args.addr.clone(), |a| a.to_string())
+    );
+    server.run(&listener, |e| eprintln!("cannot accept a connection: {e}"))
+}
@@ -0,0 +1,91 @@
+use clap::Args as ClapArgs;
+use std::{cmp::Reverse, io::Write, path::PathBuf, process::ExitCode};
+use wordle_solver::{
+    analysis,
+    share::{self, Share},
+};
+
+use super::{DayArgs, fail};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// File with the shared text [default: read standard input]
+    file: Option<PathBuf>,
+
+    /// The answer of the game [default: the answer of the puzzle the header names]
+    #[arg(short, long, conflicts_with_all = ["date", "today"])]
+    answer: Option<String>,
+
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// How many of the most common words to show for each row
+    #[arg(short = 'k', long, default_value_t = 5)]
+    examples: usize,
+}
+
+pub fn run(args: &Args) -> ExitCode {
+    let text = match &args.file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display())),
+        None => std::io::read_to_string(std::io::stdin())
+            .map_err(|e| format!("cannot read standard input: {e}")),
+    };
+    let share: Share = match text.map(|text| text.parse().map_err(|e| format!("{e}"))) {
+        Ok(Ok(share)) => share,
+        Ok(Err(e)) | Err(e) => return fail(e),
+    };
+    let answer = match (&args.answer, args.day.puzzle()) {
+        (Some(answer), _) => answer.to_ascii_lowercase(),
+        (None, Ok(Some((_, answer)))) => answer.to_string(),
+        (None, Ok(None)) => match share.answer() {
+            Some(answer) => answer.to_string(),
+            None => {
+                return fail("the share names no puzzle we know the answer of; pass --answer");
+            }
+        },
+        (None, Err(e)) => return fail(e),
+    };
+    if answer.len() != 5 || !answer.bytes().all(|b| b.is_ascii_lowercase()) {
+        return fail(format!("{answer:?} is not a five letter word"));
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    let words = analysis::words().len();
+    writeln!(
+        stdout,
+        "{}answer {answer}, {}{}",
+        share
+            .number
+            .map_or(String::new(), |n| format!("Wordle {n}, ")),
+        share
+            .guesses
+            .map_or("not solved".to_string(), |n| format!("solved in {n}")),
+        if share.hard { " in hard mode" } else { "" }
+    )
+    .expect("Failed to write to stdout");
+    for (i, row) in share.rows.iter().enumerate() {
+        let mut producers = share::producers(&answer, row);
+        producers.sort_by_key(|&(_, count)| Reverse(count));
+        let examples = Vec::from_iter(producers.iter().take(args.examples).map(|&(word, _)| word));
+        writeln!(
+            stdout,
+            "{:>2}  {}  {:>5} words ({:.1}%){}{}",
+            i + 1,
+            String::from_iter(row.iter().map(|c| c.emoji())),
+            producers.len(),
+            100.0 * producers.len() as f64 / words as f64,
+            if examples.is_empty() { "" } else { ", like " },
+            examples.join(", ")
+        )
+        .expect("Failed to write to stdout");
+        if producers.is_empty() {
+            writeln!(
+                stdout,
+                "    no word gets this against {answer}; is it the right puzzle?"
+            )
+            .expect("Failed to write to stdout");
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,30 @@
+use clap::Args as ClapArgs;
+use std::process::ExitCode;
+use wordle_solver::bench::{self, tree::Tree};
+
+use super::{GameArgs, fail, progress};
+use crate::Global;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(flatten)]
+    games: GameArgs,
+}
+
+pub fn run(global: &Global, args: &Args) -> ExitCode {
+    let answers = match args.games.select() {
+        Ok(answers) => answers,
+        Err(e) => return fail(e),
+    };
+    let games = bench::run(
+        &global.wordle(),
+        &answers,
+        args.games.jobs(),
+        || global.guesser(),
+        progress(""),
+    );
+    Tree::new(&games)
+        .write_text(std::io::stdout().lock())
+        .expect("Failed to write to stdout");
+    ExitCode::SUCCESS
+}
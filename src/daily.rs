@@ -0,0 +1,175 @@
+//! Which answer was the puzzle of the day.
+//!
+//! The answer list is in the order the original game used it, one answer a day starting with
+//! puzzle 0 on 2021-06-19.
+
+use std::{
+    fmt,
+    str::FromStr,
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The day of puzzle 0.
+pub const EPOCH: Date = Date {
+    year: 2021,
+    month: 6,
+    day: 19,
+};
+
+static ANSWERS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+/// Every answer, in puzzle order.
+pub fn answers() -> &'static [&'static str] {
+    ANSWERS.get_or_init(|| Vec::from_iter(include_str!("../answers.txt").split_whitespace()))
+}
+
+/// The number of the puzzle on `date`, or `None` outside the days the answer list covers.
+pub fn number(date: Date) -> Option<usize> {
+    let days = date.days() - EPOCH.days();
+    usize::try_from(days).ok().filter(|&n| n < answers().len())
+}
+
+/// The answer of the puzzle on `date`.
+pub fn answer(date: Date) -> Option<&'static str> {
+    number(date).map(|n| answers()[n])
+}
+
+/// A day of the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Today in UTC.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the clock is past 1970")
+            .as_secs();
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    /// Days since 1970-01-01, after Howard Hinnant's `days_from_civil`.
+    fn days(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let month = i64::from(self.month);
+        let doy =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The inverse of [`Date::days`].
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        Self {
+            year: (yoe + era * 400 + i64::from(month <= 2)) as i32,
+            month,
+            day,
+        }
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(DateError::Format);
+        };
+        let (Ok(year), Ok(month), Ok(day)) = (year.parse(), month.parse(), day.parse()) else {
+            return Err(DateError::Format);
+        };
+        if !(1..=12).contains(&month) || !(1..=Self::days_in_month(year, month)).contains(&day) {
+            return Err(DateError::NoSuchDay);
+        }
+        Ok(Self { year, month, day })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    /// Not three numbers separated by dashes.
+    Format,
+    /// The month or day is out of range, like 2023-02-29.
+    NoSuchDay,
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format => write!(f, "expected a date like 2022-01-31"),
+            Self::NoSuchDay => write!(f, "there is no such day"),
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, DateError, EPOCH, answer, number};
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn puzzles() {
+        assert_eq!(answer(EPOCH), Some("cigar"));
+        assert_eq!(answer(date("2021-06-20")), Some("rebut"));
+        assert_eq!(number(date("2022-01-01")), Some(196));
+        assert_eq!(number(date("2021-06-18")), None);
+        assert_eq!(number(date("2100-01-01")), None);
+    }
+
+    #[test]
+    fn calendar() {
+        for days in [-1, 0, 59, 365, 18_797, 19_000, 20_000, 60_000] {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+        assert_eq!(Date::from_days(0), date("1970-01-01"));
+        assert_eq!(EPOCH.days(), 18_797);
+        assert_eq!(date("2024-02-29").days() + 1, date("2024-03-01").days());
+        assert_eq!(EPOCH.to_string(), "2021-06-19");
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("2022-13-01".parse::<Date>(), Err(DateError::NoSuchDay));
+        assert_eq!("2023-02-29".parse::<Date>(), Err(DateError::NoSuchDay));
+        assert_eq!("2022/01/01".parse::<Date>(), Err(DateError::Format));
+        assert_eq!("today".parse::<Date>(), Err(DateError::Format));
+        assert!("2024-02-29".parse::<Date>().is_ok());
+    }
+}
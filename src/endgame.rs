@@ -0,0 +1,371 @@
+//! Playing the last few guesses perfectly.
+//!
+//! Scoring probes by their information is a good rule of thumb while thousands of words are
+//! left, but with a handful left it can pick a word that splits them well and still needs
+//! more guesses on average than another one would. With few enough words every strategy can
+//! be tried: [`solve`] searches them all for the guess that finds the answer in the fewest
+//! guesses on average, weighing answers by frequency, without ever running out of turns.
+//!
+//! A position where even the best strategy can run out of turns for some answer has no plan,
+//! which is how frontends tell that the game may be lost whatever is played. The search gives
+//! up once it has done [`BUDGET`] steps of work, as some sets of words near [`MAX_WORDS`] take
+//! far longer than any guess should.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::{Correctness, analysis};
+
+/// Guesses in a game of Wordle.
+pub const TURNS: usize = 6;
+
+/// How many words may be left for an exact search to still be quick.
+pub const DEFAULT_THRESHOLD: usize = 20;
+
+/// The most words [`solve`] searches over.
+pub const MAX_WORDS: usize = 128;
+
+/// How many patterns [`solve`] looks up, one per probe and word of every position it works
+/// out, before it gives up. That takes about a second.
+pub const BUDGET: usize = 1 << 26;
+
+/// The pattern index of a guess that was the answer.
+const SOLVED: u8 = 0;
+
+/// The best guess, and what the game is expected to take from here with it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plan {
+    pub guess: &'static str,
+    /// Guesses until the answer is found on average, counting this one.
+    pub expected: f64,
+}
+
+/// Why [`solve`] has no plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoPlan {
+    /// However the game is played, some answer is not found within the turns left.
+    OutOfTurns,
+    /// The search used up its [`BUDGET`] without finishing.
+    OverBudget,
+}
+
+impl fmt::Display for NoPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfTurns => write!(f, "no way of guessing is sure to find the answer in time"),
+            Self::OverBudget => write!(f, "too many ways of guessing to search them all"),
+        }
+    }
+}
+
+impl std::error::Error for NoPlan {}
+
+/// The guess that finds the answer among `remaining`, weighted by their counts, in the fewest
+/// guesses on average, while making sure it takes at most `turns` for every answer.
+///
+/// Any dictionary word may be guessed, or in hard mode only the words that may still be the
+/// answer at that point of the game, which always keep to its rules.
+///
+/// Panics if `remaining` is empty or holds more than [`MAX_WORDS`] words.
+pub fn solve(
+    remaining: &[(&'static str, usize)],
+    turns: usize,
+    hard_mode: bool,
+) -> Result<Plan, NoPlan> {
+    solve_within(remaining, turns, hard_mode, BUDGET)
+}
+
+fn solve_within(
+    remaining: &[(&'static str, usize)],
+    turns: usize,
+    hard_mode: bool,
+    budget: usize,
+) -> Result<Plan, NoPlan> {
+    assert!(!remaining.is_empty(), "no word is left to find");
+    assert!(
+        remaining.len() <= MAX_WORDS,
+        "too many words left to search exhaustively"
+    );
+    if turns == 0 {
+        return Err(NoPlan::OutOfTurns);
+    }
+    let mut search = Search::new(remaining, hard_mode, budget);
+    let everything = u128::MAX >> (u128::BITS as usize - remaining.len());
+    let best = search.best(everything, turns);
+    if search.gave_up {
+        return Err(NoPlan::OverBudget);
+    }
+    let (probe, expected) = best.ok_or(NoPlan::OutOfTurns)?;
+    Ok(Plan {
+        guess: search.probes[probe].0,
+        expected,
+    })
+}
+
+struct Search {
+    weights: Vec<f64>,
+    /// Every word worth guessing with the pattern it shows for each remaining word, as a
+    /// number in base 3. The remaining words come first, in order, and after them one word
+    /// for every other way of telling them apart.
+    probes: Vec<(&'static str, Vec<u8>)>,
+    /// Whether only the words that may still be the answer are guessed. Those are the first
+    /// probes, at the same positions as in the set of words.
+    hard_mode: bool,
+    /// The best probe and the guesses it is expected to take, by set of words and turns.
+    best: HashMap<(u128, usize), Option<(usize, f64)>>,
+    /// How many more patterns may be looked up.
+    budget: usize,
+    /// Set once a position was left unsearched for lack of budget, after which no plan
+    /// found is to be trusted.
+    gave_up: bool,
+}
+
+impl Search {
+    fn new(remaining: &[(&'static str, usize)], hard_mode: bool, budget: usize) -> Self {
+        let pattern = |probe, answer| {
+            Correctness::compute(answer, probe)
+                .iter()
+                .fold(0, |i, &c| i * 3 + c as u8)
+        };
+        let mut probes = Vec::from_iter(remaining.iter().map(|&(probe, _)| {
+            let patterns =
+                Vec::from_iter(remaining.iter().map(|&(answer, _)| pattern(probe, answer)));
+            (probe, patterns)
+        }));
+        if !hard_mode {
+            // Words that tell the remaining ones apart the same way make the same plans.
+            let mut splits = HashSet::new();
+            for &(probe, _) in analysis::words() {
+                let patterns =
+                    Vec::from_iter(remaining.iter().map(|&(answer, _)| pattern(probe, answer)));
+                if patterns.iter().any(|&p| p != patterns[0]) && splits.insert(patterns.clone()) {
+                    probes.push((probe, patterns));
+                }
+            }
+        }
+        Self {
+            weights: Vec::from_iter(remaining.iter().map(|&(_, count)| count as f64)),
+            probes,
+            hard_mode,
+            best: HashMap::new(),
+            budget,
+            gave_up: false,
+        }
+    }
+
+    fn weight(&self, words: u128) -> f64 {
+        bits(words).map(|i| self.weights[i]).sum()
+    }
+
+    /// The fewest guesses that finding an answer among `words` could possibly take on
+    /// average: guessing the likeliest one, and telling all the others apart with it.
+    fn lower_bound(&self, words: u128) -> f64 {
+        if words.count_ones() == 1 {
+            return 1.0;
+        }
+        let likeliest = bits(words).map(|i| self.weights[i]).fold(0.0, f64::max);
+        2.0 - likeliest / self.weight(words)
+    }
+
+    fn best(&mut self, words: u128, turns: usize) -> Option<(usize, f64)> {
+        if words.count_ones() == 1 {
+            return Some((words.trailing_zeros() as usize, 1.0));
+        }
+        if turns < 2 {
+            return None;
+        }
+        if let Some(&best) = self.best.get(&(words, turns)) {
+            return best;
+        }
+        let probes = match self.hard_mode {
+            true => words.count_ones() as usize,
+            false => self.probes.len(),
+        };
+        let work = probes * words.count_ones() as usize;
+        if work > self.budget {
+            self.gave_up = true;
+            return None;
+        }
+        self.budget -= work;
+
+        let total = self.weight(words);
+        // Probes that split the words the same way make the same plans, so only the first
+        // of them is tried, and the ones that look best are tried first to rule out the rest.
+        let mut buckets = [0u128; 243];
+        let mut splits = HashMap::new();
+        for probe in 0..self.probes.len() {
+            // Words ruled out by earlier guesses miss some of their hints.
+            if self.hard_mode && words & (1 << probe) == 0 {
+                continue;
+            }
+            let patterns = &self.probes[probe].1;
+            for i in bits(words) {
+                buckets[patterns[i] as usize] |= 1 << i;
+            }
+            let mut split = Vec::new();
+            for i in bits(words) {
+                let bucket = std::mem::take(&mut buckets[patterns[i] as usize]);
+                if bucket != 0 && patterns[i] != SOLVED {
+                    split.push(bucket);
+                }
+            }
+            if split == [words] {
+                // It tells nothing apart.
+                continue;
+            }
+            splits.entry(split).or_insert(probe);
+        }
+        let mut splits = Vec::from_iter(splits.into_iter().map(|(split, probe)| {
+            let split = Vec::from_iter(split.into_iter().map(|b| (b, self.weight(b) / total)));
+            // What is still to be worked out, at the least.
+            let bound = 1.0
+                + split
+                    .iter()
+                    .map(|&(b, p)| p * self.lower_bound(b))
+                    .sum::<f64>();
+            (bound, probe, split)
+        }));
+        splits.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut best: Option<(usize, f64)> = None;
+        for (mut bound, probe, split) in splits {
+            if best.is_some_and(|(_, best)| bound >= best - 1e-9) {
+                break;
+            }
+            let mut cost = Some(1.0);
+            for &(b, p) in &split {
+                if best.is_some_and(|(_, best)| bound >= best - 1e-9) {
+                    cost = None;
+                    break;
+                }
+                let Some((_, expected)) = self.best(b, turns - 1) else {
+                    cost = None;
+                    break;
+                };
+                bound += p * (expected - self.lower_bound(b));
+                cost = cost.map(|cost| cost + p * expected);
+            }
+            if let Some(cost) = cost.filter(|&cost| best.is_none_or(|(_, best)| cost < best - 1e-9))
+            {
+                best = Some((probe, cost));
+            }
+        }
+
+        self.best.insert((words, turns), best);
+        best
+    }
+}
+
+/// The positions of the set bits of `words`.
+fn bits(mut words: u128) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        (words != 0).then(|| {
+            let i = words.trailing_zeros() as usize;
+            words &= words - 1;
+            i
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NoPlan, solve, solve_within};
+    use crate::{Correctness, Guess, Wordle};
+
+    #[test]
+    fn small() {
+        assert_eq!(solve(&[("cigar", 1)], 1, true).unwrap().expected, 1.0);
+        assert_eq!(solve(&[("cigar", 1)], 0, true), Err(NoPlan::OutOfTurns));
+
+        // Either word tells the other apart: the likelier one goes first.
+        let pair = [("cigar", 1), ("rebut", 3)];
+        let plan = solve(&pair, 2, true).unwrap();
+        assert_eq!(plan.guess, "rebut");
+        assert_eq!(plan.expected, 1.25);
+        assert_eq!(solve(&pair, 1, true), Err(NoPlan::OutOfTurns));
+    }
+
+    #[test]
+    fn traps() {
+        // Guessing any of them only tells whether it was the answer.
+        let words = [
+            ("fight", 1),
+            ("light", 1),
+            ("might", 1),
+            ("night", 1),
+            ("right", 1),
+            ("sight", 1),
+            ("tight", 1),
+        ];
+        assert_eq!(solve(&words, 6, true), Err(NoPlan::OutOfTurns));
+        let hard = solve(&words, 7, true).unwrap();
+        assert!(words.iter().any(|&(word, _)| word == hard.guess));
+        assert_eq!(hard.expected, 4.0);
+
+        // Other words tell several of them apart at once.
+        let plan = solve(&words, 6, false).unwrap();
+        assert!(words.iter().all(|&(word, _)| word != plan.guess));
+        assert!(plan.expected < hard.expected);
+
+        // Too small a budget gives up rather than guess at a plan.
+        assert_eq!(solve_within(&words, 6, false, 1), Err(NoPlan::OverBudget));
+        assert_eq!(solve_within(&words, 6, false, 1 << 20), Ok(plan));
+    }
+
+    /// Follows [`solve`] to every answer among `words` under hard mode rules, and returns how
+    /// many guesses that takes on average, weighing answers as `solve` does.
+    fn play_hard(words: &[(&'static str, usize)], turns: usize) -> f64 {
+        let wordle = Wordle::new().with_hard_mode(true);
+        let mut total = 0.0;
+        for &(answer, count) in words {
+            let mut history: Vec<Guess> = Vec::new();
+            while history
+                .last()
+                .is_none_or(|g| g.mask != [Correctness::Correct; 5])
+            {
+                let left = Vec::from_iter(
+                    words
+                        .iter()
+                        .copied()
+                        .filter(|&(word, _)| history.iter().all(|g| g.matches(word))),
+                );
+                let guess = solve(&left, turns - history.len(), true).unwrap().guess;
+                assert!(wordle.allows(&history, guess), "{guess} breaks hard mode");
+                history.push(Guess {
+                    word: guess.into(),
+                    mask: Correctness::compute(answer, guess),
+                });
+            }
+            total += (count * history.len()) as f64;
+        }
+        total / words.iter().map(|&(_, count)| count as f64).sum::<f64>()
+    }
+
+    #[test]
+    fn hard_mode_follow_ups() {
+        // What tares and chimp leave for aahed. Telling some of these apart in time takes
+        // guesses that earlier hints have ruled out.
+        let words = [
+            ("aahed", 49650),
+            ("haded", 19501),
+            ("hakea", 125691),
+            ("haled", 465415),
+            ("haven", 57312614),
+            ("hawed", 162986),
+            ("hayed", 38574),
+            ("hayey", 1591),
+            ("hazed", 202410),
+            ("hazel", 12121378),
+            ("kaneh", 12582),
+        ];
+        for turns in 1..=6 {
+            match solve(&words, turns, true) {
+                Ok(plan) => assert!((play_hard(&words, turns) - plan.expected).abs() < 1e-9),
+                Err(e) => assert_eq!(e, NoPlan::OutOfTurns),
+            }
+        }
+        assert!(solve(&words, 6, true).is_ok());
+    }
+}
@@ -0,0 +1,346 @@
+//! Guessers that are separate programs, so bots written in any language can play through the
+//! same harness as the built-in ones.
+//!
+//! A bot is started once and plays game after game. For every guess it is sent one line with
+//! the game so far, as `word:pattern` pairs separated by spaces with patterns in `C`/`M`/`W`
+//! letters, and answers with a line holding its guess. An empty line starts a new game:
+//!
+//! ```text
+//! >                        a new game
+//! < tares
+//! > tares:WMMWW
+//! < cigar
+//! ```
+//!
+//! Whatever the bot writes to stderr is passed through. A bot that exits, answers with
+//! something other than a dictionary word, or takes longer than its timeout is killed, which
+//! fails the game it was playing, and the next game starts another copy.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    sync::{
+        Mutex,
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{Guess, GuessError, Guesser, Wordle};
+
+/// A bot program, and the copies of it that are running but not playing a game.
+pub struct External {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+    wordle: Wordle,
+    idle: Mutex<Vec<Process>>,
+}
+
+impl External {
+    pub fn new(
+        program: impl Into<String>,
+        args: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::from_iter(args.into_iter().map(Into::into)),
+            timeout: Duration::from_secs(10),
+            wordle: Wordle::new(),
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// How long the bot may take over one guess. Ten seconds unless set.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Starts a copy of the bot ahead of the first game, to find out early if it cannot run.
+    pub fn start(&self) -> Result<(), ExternalError> {
+        let process = Process::spawn(self)?;
+        self.idle
+            .lock()
+            .expect("no panics while locked")
+            .push(process);
+        Ok(())
+    }
+
+    /// A guesser for one game, played by an idle copy of the bot or else a new one.
+    ///
+    /// Games played at the same time each get their own copy.
+    pub fn guesser(&self) -> ExternalGuesser<'_> {
+        ExternalGuesser {
+            external: self,
+            process: None,
+        }
+    }
+}
+
+pub struct ExternalGuesser<'a> {
+    external: &'a External,
+    process: Option<Process>,
+}
+
+impl ExternalGuesser<'_> {
+    /// Asks the bot for its next guess. After an error the bot is killed, and the next guess
+    /// is asked of another copy.
+    pub fn ask(&mut self, history: &[Guess]) -> Result<String, ExternalError> {
+        let external = self.external;
+        let process = match &mut self.process {
+            Some(process) => process,
+            None => {
+                let idle = external.idle.lock().expect("no panics while locked").pop();
+                let process = match idle {
+                    Some(process) => process,
+                    None => Process::spawn(external)?,
+                };
+                self.process.insert(process)
+            }
+        };
+        let line = Vec::from_iter(history.iter().map(|guess| {
+            format!(
+                "{}:{}",
+                guess.word,
+                String::from_iter(guess.mask.iter().map(|c| c.letter()))
+            )
+        }))
+        .join(" ");
+        let guess = process.ask(&line, external.timeout).and_then(|answer| {
+            let guess = answer.trim().to_ascii_lowercase();
+            if external.wordle.is_valid(&guess) {
+                Ok(guess)
+            } else {
+                Err(ExternalError::NotAWord(answer))
+            }
+        });
+        if guess.is_err() {
+            self.process = None;
+        }
+        guess
+    }
+}
+
+impl Guesser for ExternalGuesser<'_> {
+    /// Panics if the bot misbehaves; [`try_guess`](Guesser::try_guess) fails instead.
+    fn guess(&mut self, history: &[Guess]) -> String {
+        self.try_guess(history).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+        self.ask(history)
+            .map_err(|e| GuessError(format!("bot {}: {e}", self.external.program)))
+    }
+}
+
+impl Drop for ExternalGuesser<'_> {
+    fn drop(&mut self) {
+        if let Some(process) = self.process.take() {
+            self.external
+                .idle
+                .lock()
+                .expect("no panics while locked")
+                .push(process);
+        }
+    }
+}
+
+/// One running copy of a bot.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines from the bot's stdout, read on a thread of their own so waiting on them can
+    /// time out.
+    lines: Receiver<io::Result<String>>,
+}
+
+impl Process {
+    fn spawn(external: &External) -> Result<Self, ExternalError> {
+        let mut child = Command::new(&external.program)
+            .args(&external.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(ExternalError::Spawn)?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+        })
+    }
+
+    fn ask(&mut self, line: &str, timeout: Duration) -> Result<String, ExternalError> {
+        if let Err(e) = writeln!(self.stdin, "{line}").and_then(|()| self.stdin.flush()) {
+            return Err(match e.kind() {
+                io::ErrorKind::BrokenPipe => self.exited(),
+                _ => ExternalError::Io(e),
+            });
+        }
+        match self.lines.recv_timeout(timeout) {
+            Ok(Ok(answer)) => Ok(answer),
+            Ok(Err(e)) => Err(ExternalError::Io(e)),
+            Err(RecvTimeoutError::Timeout) => Err(ExternalError::Timeout(timeout)),
+            Err(RecvTimeoutError::Disconnected) => Err(self.exited()),
+        }
+    }
+
+    fn exited(&mut self) -> ExternalError {
+        // The bot closed its end, but may not have quite finished exiting.
+        for _ in 0..20 {
+            if let Ok(Some(status)) = self.child.try_wait() {
+                return ExternalError::Exited(Some(status));
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        ExternalError::Exited(None)
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[derive(Debug)]
+pub enum ExternalError {
+    Spawn(io::Error),
+    Io(io::Error),
+    /// The bot stopped, with its exit status if it could be had.
+    Exited(Option<ExitStatus>),
+    Timeout(Duration),
+    /// The bot answered with this line, which is not a word in the dictionary.
+    NotAWord(String),
+}
+
+impl fmt::Display for ExternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spawn(e) => write!(f, "cannot start: {e}"),
+            Self::Io(e) => write!(f, "cannot talk to it: {e}"),
+            Self::Exited(Some(status)) => write!(f, "stopped mid-game ({status})"),
+            Self::Exited(None) => write!(f, "stopped mid-game"),
+            Self::Timeout(timeout) => write!(f, "no guess within {timeout:?}"),
+            Self::NotAWord(answer) => write!(f, "guessed {answer:?}, which is not a word"),
+        }
+    }
+}
+
+impl std::error::Error for ExternalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Spawn(e) | Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{External, ExternalError};
+    use crate::{Correctness, Guess, Wordle, bench};
+    use std::time::Duration;
+
+    fn bot(script: &str) -> External {
+        External::new("sh", ["-c", script]).with_timeout(Duration::from_millis(500))
+    }
+
+    #[test]
+    fn plays() {
+        // Opens with tares and then always guesses cigar.
+        let external = bot(r#"while read -r line; do
+            if [ -z "$line" ]; then echo TARES; else echo cigar; fi
+        done"#);
+        external.start().unwrap();
+        let wordle = Wordle::new();
+        assert_eq!(wordle.play("cigar", external.guesser()), Some(2));
+        assert_eq!(wordle.play("tares", external.guesser()), Some(1));
+        // Both games were played by the bot started up front.
+        assert_eq!(external.idle.lock().unwrap().len(), 1);
+
+        let mut first = external.guesser();
+        let mut second = external.guesser();
+        assert_eq!(first.ask(&[]).unwrap(), "tares");
+        assert_eq!(second.ask(&[]).unwrap(), "tares");
+        drop((first, second));
+        assert_eq!(external.idle.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn history() {
+        let external = bot(r#"while read -r line; do
+            case "$line" in "") echo tares ;; "tares:WMMWW") echo cigar ;; *) echo rebut ;; esac
+        done"#);
+        let mut guesser = external.guesser();
+        assert_eq!(guesser.ask(&[]).unwrap(), "tares");
+        let history = [Guess {
+            word: "tares".into(),
+            mask: Correctness::compute("cigar", "tares"),
+        }];
+        assert_eq!(guesser.ask(&history).unwrap(), "cigar");
+    }
+
+    #[test]
+    fn misbehaving() {
+        let external = bot("exit 3");
+        assert!(matches!(
+            external.guesser().ask(&[]),
+            Err(ExternalError::Exited(Some(status))) if status.code() == Some(3)
+        ));
+        let external = bot("read -r line; exec sleep 5");
+        assert!(matches!(
+            external.guesser().ask(&[]),
+            Err(ExternalError::Timeout(_))
+        ));
+        let external = bot("read -r line; echo zzzzz; read -r line");
+        assert!(matches!(
+            external.guesser().ask(&[]),
+            Err(ExternalError::NotAWord(answer)) if answer == "zzzzz"
+        ));
+        // A bot that failed is not handed to the next game.
+        assert!(external.idle.lock().unwrap().is_empty());
+        let external = External::new("/no/such/bot", [""; 0]);
+        assert!(matches!(external.start(), Err(ExternalError::Spawn(_))));
+    }
+
+    #[test]
+    fn fails_games() {
+        // Answers three lines and then stalls.
+        let external = bot(r#"n=0; while read -r line; do
+            n=$((n + 1)); [ $n -gt 3 ] && exec sleep 5
+            if [ -z "$line" ]; then echo tares; else echo cigar; fi
+        done"#);
+        let answers = ["cigar", "rebut", "sissy"];
+        let games = bench::run(
+            &Wordle::new(),
+            &answers,
+            1,
+            || external.guesser(),
+            |_, _| {},
+        );
+        assert_eq!(games[0].guesses, Some(2));
+        assert_eq!(games[0].error, None);
+        // The stalled bot fails its game, and a new one plays the next.
+        assert_eq!(games[1].words, ["tares"]);
+        assert_eq!(
+            games[1].error.as_deref(),
+            Some("bot sh: no guess within 500ms")
+        );
+        assert_eq!(games[2].words, ["tares", "cigar", "cigar"]);
+        assert!(games[2].error.is_some());
+    }
+}
@@ -1,43 +1,244 @@
-use std::{borrow::Cow, collections::HashSet};
+//! Solvers for Wordle, and the harness that plays and measures them.
+//!
+//! Without the default `std` feature only the core is left: [`Correctness`], [`Guess`], the
+//! [`Guesser`] trait with its [`GuessError`], and [`rng`], which need nothing but an allocator.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{collections::HashSet, sync::OnceLock};
+
+#[cfg(test)]
+macro_rules! mask {
+    (C) => {crate::Correctness::Correct};
+    (M) => {crate::Correctness::Misplaced};
+    (W) => {crate::Correctness::Wrong};
+    ($($c:tt)+) => {[
+        $(mask!($c)),+
+    ]}
+}
+
+#[cfg(feature = "std")]
 pub mod algorithms;
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "std")]
+pub mod book;
+#[cfg(feature = "std")]
+pub mod daily;
+#[cfg(feature = "std")]
+pub mod endgame;
+#[cfg(feature = "std")]
+pub mod external;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "std")]
+pub mod memo;
+#[cfg(feature = "std")]
+pub mod openers;
+#[cfg(feature = "std")]
+pub mod parallel;
+#[cfg(feature = "std")]
+pub mod protocol;
+#[cfg(feature = "std")]
+pub mod registry;
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod server;
+#[cfg(feature = "std")]
+pub mod session;
+#[cfg(feature = "std")]
+pub mod share;
+#[cfg(feature = "std")]
+pub mod solver;
+
+#[cfg(feature = "std")]
+static DICTIONARY: OnceLock<&'static str> = OnceLock::new();
+
+#[cfg(feature = "std")]
+/// The dictionary every game and guesser draws from, one `word frequency` pair per line.
+fn dictionary() -> &'static str {
+    DICTIONARY.get_or_init(|| include_str!("../dictionary.txt"))
+}
+
+#[cfg(feature = "std")]
+/// Replaces the built-in dictionary with `text`, in the same `word frequency` line format.
+///
+/// Guessers cache the word list the first time one is built, so this has to be called before
+/// any `Wordle` or guesser is created.
+pub fn use_dictionary(text: &'static str) -> Result<(), DictionaryError> {
+    for (i, line) in text.lines().enumerate() {
+        let valid = line.split_once(' ').is_some_and(|(word, count)| {
+            word.len() == 5
+                && word.bytes().all(|b| b.is_ascii_lowercase())
+                && count.parse::<usize>().is_ok()
+        });
+        if !valid {
+            return Err(DictionaryError::Malformed { line: i + 1 });
+        }
+    }
+    if text.lines().next().is_none() {
+        return Err(DictionaryError::Empty);
+    }
+    DICTIONARY
+        .set(text)
+        .map_err(|_| DictionaryError::AlreadyInUse)
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictionaryError {
+    /// The line is not a five letter lowercase word, a space and a frequency count.
+    Malformed {
+        line: usize,
+    },
+    Empty,
+    /// A dictionary was already in use by the time another was loaded.
+    AlreadyInUse,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed { line } => {
+                write!(
+                    f,
+                    "line {line}: expected a five letter word and a frequency"
+                )
+            }
+            Self::Empty => write!(f, "the dictionary has no words"),
+            Self::AlreadyInUse => write!(f, "a dictionary is already in use"),
+        }
+    }
+}
 
-const DICTIONARY: &str = include_str!("../dictionary.txt");
+#[cfg(feature = "std")]
+impl std::error::Error for DictionaryError {}
 
+#[cfg(feature = "std")]
 pub struct Wordle {
     dictionary: HashSet<&'static str>,
+    hard: bool,
 }
 
+#[cfg(feature = "std")]
 impl Wordle {
     pub fn new() -> Self {
         Self {
             dictionary: HashSet::from_iter(
-                DICTIONARY
+                dictionary()
                     .lines()
                     .map(|line| line.split_once(' ').unwrap().0),
             ),
+            hard: false,
         }
     }
 
-    pub fn play<G: Guesser>(&self, answer: &'static str, mut guessers: G) -> Option<usize> {
+    /// Plays by hard mode rules, where every revealed hint must be used in later guesses.
+    pub fn with_hard_mode(mut self, hard: bool) -> Self {
+        self.hard = hard;
+        self
+    }
+
+    pub fn is_hard_mode(&self) -> bool {
+        self.hard
+    }
+
+    /// Whether `word` is in the dictionary.
+    pub fn is_valid(&self, word: &str) -> bool {
+        self.dictionary.contains(word)
+    }
+
+    /// Whether `word` may be guessed next, given the game so far.
+    pub fn allows(&self, history: &[Guess], word: &str) -> bool {
+        self.is_valid(word) && (!self.hard || history.iter().all(|g| g.uses_hints(word)))
+    }
+
+    /// Plays `guesser` until it finds `answer`, or `None` if it has not after 32 guesses. A
+    /// game that ends in an error counts as lost.
+    pub fn play<G: Guesser>(&self, answer: &'static str, guesser: G) -> Option<usize> {
+        self.try_play(answer, guesser).ok().flatten()
+    }
+
+    /// Like [`play`](Self::play), but tells why a game ended early: the guesser failed, or
+    /// played a word the rules do not allow.
+    pub fn try_play<G: Guesser>(
+        &self,
+        answer: &'static str,
+        mut guesser: G,
+    ) -> Result<Option<usize>, PlayError> {
         let mut history = Vec::new();
         for i in 1..=32 {
-            let guess = guessers.guess(&history);
+            let guess = guesser.try_guess(&history).map_err(PlayError::Guesser)?;
+            if !self.is_valid(&guess) {
+                return Err(PlayError::NotAWord(guess));
+            }
+            if !self.allows(&history, &guess) {
+                return Err(PlayError::HardMode(guess));
+            }
             if guess == answer {
-                return Some(i);
+                return Ok(Some(i));
             }
-            assert!(self.dictionary.contains(guess.as_str()));
             let correctness = Correctness::compute(answer, &guess);
             history.push(Guess {
                 word: Cow::Owned(guess),
                 mask: correctness,
             });
         }
-        None
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "std")]
+/// Why a game ended before it was won or lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayError {
+    Guesser(GuessError),
+    NotAWord(String),
+    /// The word does not use every hint so far, in a hard mode game.
+    HardMode(String),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Guesser(e) => write!(f, "{e}"),
+            Self::NotAWord(word) => write!(f, "guessed {word:?}, which is not in the dictionary"),
+            Self::HardMode(word) => write!(
+                f,
+                "guessed {word}, which does not use every hint, as hard mode requires"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PlayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Guesser(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Wordle {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Correctness {
     Correct,
     Misplaced,
@@ -77,6 +278,40 @@ impl Correctness {
         c
     }
 
+    /// The letter the interactive helper uses for this: `C`, `M` or `W`.
+    pub fn letter(self) -> char {
+        match self {
+            Self::Correct => 'C',
+            Self::Misplaced => 'M',
+            Self::Wrong => 'W',
+        }
+    }
+
+    /// The square a Wordle share grid uses for this.
+    pub fn emoji(self) -> char {
+        match self {
+            Self::Correct => '🟩',
+            Self::Misplaced => '🟨',
+            Self::Wrong => '⬛',
+        }
+    }
+
+    /// Reads a pattern as people type or paste it: `C`/`M`/`W`, `g`/`y`/`b` for green, yellow
+    /// and black, or share-grid squares including the high contrast ones, in either case and
+    /// with any spacing.
+    pub fn parse_pattern(pattern: &str) -> Option<[Self; 5]> {
+        let mut mask = Vec::with_capacity(5);
+        for c in pattern.chars().filter(|c| !c.is_whitespace()) {
+            mask.push(match c.to_ascii_lowercase() {
+                'c' | 'g' | '🟩' | '🟧' => Self::Correct,
+                'm' | 'y' | '🟨' | '🟦' => Self::Misplaced,
+                'w' | 'b' | '⬛' | '⬜' => Self::Wrong,
+                _ => return None,
+            });
+        }
+        mask.try_into().ok()
+    }
+
     pub fn patterns() -> impl Iterator<Item = [Self; 5]> {
         itertools::iproduct!(
             [Self::Correct, Self::Misplaced, Self::Wrong],
@@ -89,12 +324,37 @@ impl Correctness {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Guess<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub word: Cow<'a, str>,
     pub mask: [Correctness; 5],
 }
 impl Guess<'_> {
-    fn matches(&self, word: &str) -> bool {
+    /// Whether `word` keeps every green of this guess in place and contains every yellow, as
+    /// hard mode requires. Grey letters may be played again.
+    pub fn uses_hints(&self, word: &str) -> bool {
+        let mut unused = [0u8; 256];
+        for (i, (g, w)) in self.word.bytes().zip(word.bytes()).enumerate() {
+            match self.mask[i] {
+                Correctness::Correct if g != w => return false,
+                Correctness::Correct => {}
+                _ => unused[w as usize] += 1,
+            }
+        }
+        self.word.bytes().zip(self.mask).all(|(g, c)| {
+            c != Correctness::Misplaced || {
+                let left = &mut unused[g as usize];
+                let found = *left > 0;
+                *left = left.saturating_sub(1);
+                found
+            }
+        })
+    }
+
+    /// Whether `word` could be the answer, given the pattern this guess got.
+    pub fn matches(&self, word: &str) -> bool {
         assert_eq!(self.word.len(), 5);
         assert_eq!(word.len(), 5);
         let mut used = [false; 5];
@@ -138,8 +398,37 @@ impl Guess<'_> {
 
 pub trait Guesser {
     fn guess(&mut self, history: &[Guess]) -> String;
+
+    /// Like [`guess`](Self::guess), but gives up with an error instead of panicking. Only
+    /// guessers that can fail, like other programs, need to override it.
+    fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+        Ok(self.guess(history))
+    }
+}
+
+impl Guesser for alloc::boxed::Box<dyn Guesser + '_> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        (**self).guess(history)
+    }
+
+    fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+        (**self).try_guess(history)
+    }
+}
+
+/// Why a guesser could not come up with a guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuessError(pub String);
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for GuessError {}
+
 impl<T> Guesser for T
 where
     T: Fn(&[Guess]) -> String,
@@ -149,16 +438,6 @@ where
     }
 }
 
-#[cfg(test)]
-macro_rules! mask {
-    (C) => {crate::Correctness::Correct};
-    (M) => {crate::Correctness::Misplaced};
-    (W) => {crate::Correctness::Wrong};
-    ($($c:tt)+) => {[
-        $(mask!($c)),+
-    ]}
-}
-
 #[cfg(test)]
 mod tests {
 
@@ -168,14 +447,14 @@ mod tests {
         macro_rules! check {
             ($prev:literal + [$($mask:tt)+] allows $next:literal) => {
                 assert!(Guess {
-                    word: std::borrow::Cow::Borrowed($prev),
+                    word: alloc::borrow::Cow::Borrowed($prev),
                     mask: mask![$($mask )+]
                 }
                 .matches($next));
             };
             ($prev:literal + [$($mask:tt)+] disallows $next:literal) => {
                 assert!(!Guess {
-                    word: std::borrow::Cow::Borrowed($prev),
+                    word: alloc::borrow::Cow::Borrowed($prev),
                     mask: mask![$($mask )+]
                 }
                 .matches($next));
@@ -195,6 +474,41 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "std")]
+    mod hard_mode {
+        use crate::{Guess, Wordle};
+        use std::borrow::Cow;
+
+        fn guess(word: &'static str, mask: [crate::Correctness; 5]) -> Guess<'static> {
+            Guess {
+                word: Cow::Borrowed(word),
+                mask,
+            }
+        }
+
+        #[test]
+        fn uses_hints() {
+            assert!(guess("tares", mask![C M W W W]).uses_hints("tonal"));
+            assert!(guess("tares", mask![C M W W W]).uses_hints("tasty"));
+            assert!(!guess("tares", mask![C M W W W]).uses_hints("thorn"));
+            assert!(!guess("tares", mask![C M W W W]).uses_hints("batch"));
+            assert!(guess("aabbb", mask![M M W W W]).uses_hints("xaxax"));
+            assert!(!guess("aabbb", mask![M M W W W]).uses_hints("xaxxx"));
+            assert!(!guess("aabbb", mask![C M W W W]).uses_hints("xaxxx"));
+        }
+
+        #[test]
+        fn enforced() {
+            let w = Wordle::new().with_hard_mode(true);
+            let history = [guess("tares", mask![W W W W C])];
+            assert!(w.allows(&history, "lions"));
+            assert!(!w.allows(&history, "lionz"));
+            assert!(!w.allows(&history, "olive"));
+            assert!(Wordle::new().allows(&history, "olive"));
+        }
+    }
+
+    #[cfg(feature = "std")]
     mod game {
         use crate::{Guess, Wordle};
 
@@ -279,6 +593,60 @@ mod tests {
         }
     }
 
+    mod parse_pattern {
+        use crate::Correctness;
+
+        #[test]
+        fn notations() {
+            assert_eq!(Correctness::parse_pattern("CMWWC"), Some(mask![C M W W C]));
+            assert_eq!(
+                Correctness::parse_pattern("c m w w c"),
+                Some(mask![C M W W C])
+            );
+            assert_eq!(Correctness::parse_pattern("GYBBG"), Some(mask![C M W W C]));
+            assert_eq!(
+                Correctness::parse_pattern("🟩🟨⬛⬜🟩"),
+                Some(mask![C M W W C])
+            );
+            assert_eq!(
+                Correctness::parse_pattern("🟧🟦⬛⬜🟧"),
+                Some(mask![C M W W C])
+            );
+        }
+
+        #[test]
+        fn rejects() {
+            assert_eq!(Correctness::parse_pattern("CMWW"), None);
+            assert_eq!(Correctness::parse_pattern("CMWWCC"), None);
+            assert_eq!(Correctness::parse_pattern("CMWWX"), None);
+            assert_eq!(Correctness::parse_pattern(""), None);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        use crate::{Correctness, Guess};
+
+        #[test]
+        fn round_trip() {
+            let guess = Guess {
+                word: "tares".into(),
+                mask: mask![C M W W W],
+            };
+            let json = serde_json::to_string(&guess).unwrap();
+            assert_eq!(
+                json,
+                r#"{"word":"tares","mask":["Correct","Misplaced","Wrong","Wrong","Wrong"]}"#
+            );
+            let back: Guess = serde_json::from_str(&json).unwrap();
+            assert_eq!((back.word.as_ref(), back.mask), ("tares", guess.mask));
+            assert_eq!(
+                serde_json::from_str::<Correctness>(r#""Wrong""#).unwrap(),
+                Correctness::Wrong
+            );
+        }
+    }
+
     mod compute {
         use crate::Correctness;
 
@@ -1,141 +1,279 @@
-use clap::{Parser, ValueEnum};
-use std::io::Write;
-use wordle_solver::{Correctness, Guess, Guesser, Wordle};
+use clap::{
+    Args, Parser, Subcommand,
+    builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+};
+use std::{path::PathBuf, process::ExitCode, sync::OnceLock, time::Duration};
+use wordle_solver::{
+    Guesser, Wordle,
+    book::Book,
+    endgame,
+    external::External,
+    memo,
+    registry::{Algorithm, Options, Registry},
+};
 
-const GAMES: &str = include_str!("../answers.txt");
+mod cmd;
 
 #[derive(Parser)]
 #[command(name = "wordle_solver", version = "0.1.0", author = "HuaGu_Dragon")]
 struct Cli {
-    #[clap(value_enum, short, long, default_value_t = Implementation::Native)]
-    implementation: Implementation,
+    #[command(subcommand)]
+    command: Command,
 
-    #[clap(short, long, default_value_t = false)]
-    guess: bool,
+    #[command(flatten)]
+    global: Global,
+}
+
+// Options every mode shares.
+#[derive(Args)]
+struct Global {
+    /// The guesser to use
+    #[arg(short, long = "impl", value_parser = algorithm(), global = true, default_value = "native")]
+    implementation: &'static Algorithm,
+
+    /// Use this list of `word frequency` lines instead of the built-in dictionary
+    #[arg(long, value_name = "FILE", global = true)]
+    dictionary: Option<PathBuf>,
+
+    /// Play by hard mode rules: every revealed hint must be used in later guesses
+    #[arg(long, global = true)]
+    hard: bool,
+
+    /// Worker threads used to score candidates
+    #[arg(short, long, global = true, default_value_t = 1)]
+    threads: usize,
+
+    /// Open every game with this word instead of the guesser's own first guess
+    #[arg(long, value_name = "WORD", global = true, conflicts_with = "bot")]
+    opener: Option<String>,
+
+    /// Guess with this program instead, split on whitespace into the program and its arguments
+    #[arg(long, value_name = "COMMAND", global = true)]
+    bot: Option<String>,
+
+    /// Seconds the bot may take over one guess
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        global = true,
+        default_value_t = 10.0,
+        requires = "bot"
+    )]
+    bot_timeout: f64,
+
+    /// Play perfectly once at most this many words are left, if the answer can be found in time
+    /// and the search does not take too long
+    #[arg(
+        long,
+        value_name = "WORDS",
+        global = true,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "20",
+        value_parser = clap::value_parser!(u8).range(1..=endgame::MAX_WORDS as i64),
+        conflicts_with = "bot"
+    )]
+    endgame: Option<u8>,
+
+    #[arg(skip)]
+    external: Option<External>,
+
+    /// Play the first guesses from this opening book, made by the `book` command
+    #[arg(long = "book", value_name = "FILE", global = true)]
+    book_file: Option<PathBuf>,
+
+    #[arg(skip)]
+    book: Option<Book>,
 
-    #[arg(short, long)]
-    max: Option<usize>,
+    /// Remember up to this many guesses to share between games, or 0 for none
+    #[arg(long, value_name = "ENTRIES", global = true, default_value_t = memo::DEFAULT_CAPACITY)]
+    memo: usize,
 }
 
-#[derive(ValueEnum, Clone, Copy)]
-enum Implementation {
-    Native,
-    Allocs,
-    Vexer,
-    Once,
-    Precalc,
-    Weight,
-    Prune,
-    Cutoff,
+impl Global {
+    fn wordle(&self) -> Wordle {
+        Wordle::new().with_hard_mode(self.hard)
+    }
+
+    fn guesser(&self) -> Box<dyn Guesser + '_> {
+        let guesser: Box<dyn Guesser + '_> = match &self.external {
+            Some(external) => Box::new(external.guesser()),
+            None => (self.implementation.new)(&self.options()),
+        };
+        match &self.book {
+            Some(book) => Box::new(book.guesser(guesser)),
+            None => guesser,
+        }
+    }
+
+    /// How to set up a built-in guesser.
+    fn options(&self) -> Options {
+        Options {
+            threads: self.threads,
+            opener: self.opener.clone(),
+            endgame: self.endgame.map_or(0, usize::from),
+            hard_mode: self.hard,
+        }
+    }
+
+    /// What the guesser is called in reports, baselines and books, with every setting that
+    /// changes how it plays.
+    fn name(&self) -> String {
+        match &self.bot {
+            // Baseline files separate their fields by whitespace.
+            Some(command) => {
+                Vec::from_iter(command.split_whitespace()).join("_") + &self.settings()
+            }
+            None => self.name_of(self.implementation),
+        }
+    }
+
+    /// What `algorithm` is called when set up by these options, like [`name`](Self::name).
+    fn name_of(&self, algorithm: &Algorithm) -> String {
+        algorithm.name.to_string() + &self.settings()
+    }
+
+    /// The suffix naming every setting that changes how the guesser plays.
+    fn settings(&self) -> String {
+        let mut settings = String::new();
+        if let Some(opener) = &self.opener {
+            settings += &format!("@{opener}");
+        }
+        if let Some(words) = self.endgame {
+            settings += &format!("+endgame{words}");
+        }
+        if self.hard {
+            settings += "+hard";
+        }
+        // Booked games can go on differently from the guesser's own.
+        if self.book.is_some() {
+            settings += "+book";
+        }
+        settings
+    }
 }
 
-fn main() {
-    let cli = Cli::parse();
-
-    match cli.implementation {
-        Implementation::Native => start(
-            cli.guess,
-            wordle_solver::algorithms::native::Native::new,
-            cli.max,
-        ),
-        Implementation::Allocs => start(
-            cli.guess,
-            wordle_solver::algorithms::allocs::Allocs::new,
-            cli.max,
-        ),
-        Implementation::Vexer => start(
-            cli.guess,
-            wordle_solver::algorithms::vexer::Vexer::new,
-            cli.max,
-        ),
-        Implementation::Once => start(
-            cli.guess,
-            wordle_solver::algorithms::once::Once::new,
-            cli.max,
-        ),
-        Implementation::Precalc => start(
-            cli.guess,
-            wordle_solver::algorithms::precalc::Precalc::new,
-            cli.max,
-        ),
-        Implementation::Weight => start(
-            cli.guess,
-            wordle_solver::algorithms::weight::Weight::new,
-            cli.max,
-        ),
-        Implementation::Prune => start(
-            cli.guess,
-            wordle_solver::algorithms::prune::Prune::new,
-            cli.max,
-        ),
-        Implementation::Cutoff => start(
-            cli.guess,
-            wordle_solver::algorithms::cutoff::Cutoff::new,
-            cli.max,
-        ),
-    };
+#[derive(Subcommand)]
+enum Command {
+    /// Play the guesser against the answer list and report how it did
+    Bench(cmd::bench::Args),
+    /// Benchmark several implementations on the same answers and compare them game by game
+    Compare(cmd::compare::Args),
+    /// Suggest guesses for a game you are playing elsewhere
+    Assist(cmd::assist::Args),
+    /// Play a game yourself, with the program picking the answer
+    Play(cmd::play::Args),
+    /// Rate a finished game guess by guess against what the guesser would have played
+    Analyze(cmd::analyze::Args),
+    /// Work out which words could have produced each row of a shared result
+    Share(cmd::share::Args),
+    /// Guess the answer behind several share grids of the same puzzle
+    Deduce(cmd::deduce::Args),
+    /// Print the decision tree the guesser follows over the answer list
+    SolveTree(cmd::tree::Args),
+    /// Answer commands on stdin with JSON lines on stdout, for bots and other programs
+    Protocol,
+    /// Serve suggestions, scoring and hosted games over HTTP
+    Serve(cmd::serve::Args),
+    /// List the guessers to pick from, with what each of them can do
+    Algorithms,
+    /// Work out the first guesses of the guesser ahead of time, for --book
+    Book(cmd::book::Args),
+    /// Rate every word as the first guess and print the best
+    Openers(cmd::openers::Args),
 }
 
-fn start<G: Guesser>(gus: bool, mk: impl FnMut() -> G, max: Option<usize>) {
-    if gus {
-        guess(mk);
-    } else {
-        play(mk, max);
-    }
+/// The algorithms `--impl` and `compare` pick from.
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::builtin)
 }
 
-fn play<G: Guesser>(mut mk: impl FnMut() -> G, max: Option<usize>) {
-    let wordle = Wordle::new();
-    let mut score = 0;
-    let mut games = 0;
-    for answer in GAMES.split_whitespace().take(max.unwrap_or(usize::MAX)) {
-        let guesser = mk();
-        if let Some(time) = wordle.play(answer, guesser) {
-            games += 1;
-            score += time;
-            println!("Solved {answer} in {time} guesses");
-        } else {
-            println!("Failed to solve {answer}");
+/// Parses an algorithm name, listing every registered one in `--help`.
+fn algorithm() -> impl TypedValueParser<Value = &'static Algorithm> {
+    PossibleValuesParser::new(
+        registry()
+            .iter()
+            .map(|a| PossibleValue::new(a.name).help(a.description))
+            .chain(
+                registry().aliases().map(|(alias, name)| {
+                    PossibleValue::new(alias).help(format!("Plays as {name}"))
+                }),
+            ),
+    )
+    .map(|name| registry().get(&name).expect("only registered names parse"))
+}
+
+fn main() -> ExitCode {
+    let mut cli = Cli::parse();
+
+    if let Some(path) = &cli.global.dictionary {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => return cmd::fail(format!("cannot read {}: {e}", path.display())),
+        };
+        if let Err(e) = wordle_solver::use_dictionary(text.leak()) {
+            return cmd::fail(format!("{}: {e}", path.display()));
         }
     }
-    println!(
-        "Average: {:.2} guesses per game",
-        score as f64 / games as f64
-    );
-}
 
-fn guess<G: Guesser>(mut mk: impl FnMut() -> G) {
-    let mut history = Vec::new();
-    let mut guesser = mk();
-    for _ in 0..6 {
-        let guess = guesser.guess(&history);
-
-        let mut stdout = std::io::stdout();
-        writeln!(
-        stdout,
-        "Guess: {guess}\nPlease enter the correctness pattern (C for Correct, M for Misplaced, W for Wrong):"
-    ).expect("Failed to write to stdout");
-
-        let stdin = std::io::stdin();
-        let mut pattern = String::new();
-        stdin.read_line(&mut pattern).expect("Failed to read line");
-        let mask = pattern
-            .trim()
-            .bytes()
-            .filter(|v| !v.is_ascii_whitespace())
-            .map(|c| match c {
-                b'C' => Correctness::Correct,
-                b'M' => Correctness::Misplaced,
-                b'W' => Correctness::Wrong,
-                c => panic!("Invalid character in pattern {c}"),
-            })
-            .collect::<Vec<_>>()
-            .try_into()
-            .expect("Pattern must be 5 characters long");
-
-        history.push(Guess {
-            word: std::borrow::Cow::Owned(guess),
-            mask,
-        });
+    memo::set_capacity(cli.global.memo);
+
+    if let Some(opener) = &mut cli.global.opener {
+        opener.make_ascii_lowercase();
+        if !Wordle::new().is_valid(opener) {
+            return cmd::fail(format!("--opener {opener} is not in the dictionary"));
+        }
+    }
+
+    if let Some(command) = &cli.global.bot {
+        let mut words = command.split_whitespace();
+        let Some(program) = words.next() else {
+            return cmd::fail("--bot needs a command");
+        };
+        let Ok(timeout) = Duration::try_from_secs_f64(cli.global.bot_timeout) else {
+            return cmd::fail("--bot-timeout must be a number of seconds");
+        };
+        let external = External::new(program, words).with_timeout(timeout);
+        if let Err(e) = external.start() {
+            return cmd::fail(format!("bot {program}: {e}"));
+        }
+        cli.global.external = Some(external);
+    }
+
+    if let Some(path) = &cli.global.book_file {
+        let book: Book = match std::fs::read_to_string(path) {
+            Ok(text) => match text.parse() {
+                Ok(book) => book,
+                Err(e) => return cmd::fail(format!("{}: {e}", path.display())),
+            },
+            Err(e) => return cmd::fail(format!("cannot read {}: {e}", path.display())),
+        };
+        // Named before the book is set, which adds it to the name.
+        let name = cli.global.name();
+        if book.name() != name {
+            return cmd::fail(format!(
+                "{} was made for {}, not {name}",
+                path.display(),
+                book.name()
+            ));
+        }
+        cli.global.book = Some(book);
+    }
+
+    match cli.command {
+        Command::Bench(args) => cmd::bench::run(&cli.global, &args),
+        Command::Compare(args) => cmd::compare::run(&cli.global, &args),
+        Command::Assist(args) => cmd::assist::run(&cli.global, &args),
+        Command::Play(args) => cmd::play::run(&cli.global, &args),
+        Command::Analyze(args) => cmd::analyze::run(&cli.global, &args),
+        Command::Share(args) => cmd::share::run(&args),
+        Command::Deduce(args) => cmd::deduce::run(&cli.global, &args),
+        Command::SolveTree(args) => cmd::tree::run(&cli.global, &args),
+        Command::Protocol => cmd::protocol::run(&cli.global),
+        Command::Serve(args) => cmd::serve::run(&cli.global, &args),
+        Command::Algorithms => cmd::algorithms::run(registry()),
+        Command::Book(args) => cmd::book::run(&cli.global, &args),
+        Command::Openers(args) => cmd::openers::run(&cli.global, &args),
     }
 }
@@ -0,0 +1,200 @@
+//! Guesses remembered across games, by the words that were left when they were made.
+//!
+//! A benchmark builds a new guesser for every answer, and many answers lead to the same words
+//! being left, so the same guess would be worked out again and again. [`Solver`] looks its
+//! guess up here first, when its scorer says the guess depends on nothing but those words.
+//!
+//! Guesses are kept by the whole set of words, not a hash of it, so two sets can never be
+//! mistaken for each other. The memo is shared by every thread of the process. Once full, the
+//! oldest guesses are forgotten first.
+//!
+//! [`Solver`]: crate::solver::Solver
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque, hash_map::Entry},
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// How many guesses are remembered unless [`set_capacity`] says otherwise.
+pub const DEFAULT_CAPACITY: usize = 1 << 16;
+
+static MEMO: OnceLock<Mutex<Memo>> = OnceLock::new();
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+struct Memo {
+    capacity: usize,
+    guesses: HashMap<Key, String>,
+    /// Keys in the order they were added, to forget the oldest first.
+    order: VecDeque<Key>,
+}
+
+fn memo() -> &'static Mutex<Memo> {
+    MEMO.get_or_init(|| {
+        Mutex::new(Memo {
+            capacity: DEFAULT_CAPACITY,
+            guesses: HashMap::new(),
+            order: VecDeque::new(),
+        })
+    })
+}
+
+/// Which algorithm made a guess, and the words that were left, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Key {
+    algorithm: Cow<'static, str>,
+    words: Box<[&'static str]>,
+}
+
+impl Key {
+    pub(crate) fn new(algorithm: Cow<'static, str>, remaining: &[(&'static str, usize)]) -> Self {
+        Self {
+            algorithm,
+            words: remaining.iter().map(|&(word, _)| word).collect(),
+        }
+    }
+}
+
+/// The guess remembered under `key`. A memo that is turned off is not looked in, and the
+/// lookup is not counted.
+pub(crate) fn get(key: &Key) -> Option<String> {
+    let memo = memo().lock().expect("no panics while locked");
+    if memo.capacity == 0 {
+        return None;
+    }
+    let guess = memo.guesses.get(key).cloned();
+    drop(memo);
+    match guess {
+        Some(_) => HITS.fetch_add(1, Ordering::Relaxed),
+        None => MISSES.fetch_add(1, Ordering::Relaxed),
+    };
+    guess
+}
+
+pub(crate) fn insert(key: Key, guess: &str) {
+    memo()
+        .lock()
+        .expect("no panics while locked")
+        .insert(key, guess);
+}
+
+impl Memo {
+    fn insert(&mut self, key: Key, guess: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Another thread may have worked out the same guess in the meantime.
+        if let Entry::Vacant(entry) = self.guesses.entry(key.clone()) {
+            entry.insert(guess.to_string());
+            self.order.push_back(key);
+            self.shrink();
+        }
+    }
+
+    fn shrink(&mut self) {
+        while self.order.len() > self.capacity {
+            let oldest = self.order.pop_front().expect("longer than the capacity");
+            self.guesses.remove(&oldest);
+        }
+    }
+}
+
+/// Remembers at most `entries` guesses from now on, forgetting the oldest ones beyond that.
+/// Zero turns the memo off.
+pub fn set_capacity(entries: usize) {
+    let mut memo = memo().lock().expect("no panics while locked");
+    memo.capacity = entries;
+    memo.shrink();
+}
+
+/// How often guesses were found, since the process started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Stats {
+    /// The lookups between `earlier` and these stats.
+    pub fn since(self, earlier: Stats) -> Stats {
+        Stats {
+            hits: self.hits - earlier.hits,
+            misses: self.misses - earlier.misses,
+        }
+    }
+
+    /// The share of lookups that found a guess, or zero if there were none.
+    pub fn hit_rate(self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+pub fn stats() -> Stats {
+    Stats {
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Key, Memo, Stats, get, insert};
+
+    fn key(algorithm: &'static str, words: &[(&'static str, usize)]) -> Key {
+        Key::new(algorithm.into(), words)
+    }
+
+    #[test]
+    fn remembers() {
+        let words = [("cigar", 3), ("rebut", 2)];
+        let cigar = key("tests::remembers", &words);
+        assert_eq!(
+            cigar,
+            key("tests::remembers", &[("cigar", 7), ("rebut", 1)])
+        );
+        assert_ne!(cigar, key("tests::remembers", &words[..1]));
+        assert_ne!(cigar, key("tests::other", &words));
+
+        assert_eq!(get(&cigar), None);
+        insert(cigar.clone(), "cigar");
+        insert(cigar.clone(), "rebut");
+        assert_eq!(get(&cigar).as_deref(), Some("cigar"));
+    }
+
+    #[test]
+    fn bounded() {
+        // A memo of its own, so tests sharing the process-wide one do not get in the way.
+        let mut memo = Memo {
+            capacity: 2,
+            guesses: Default::default(),
+            order: Default::default(),
+        };
+        let keys = ["a", "b", "c"].map(|algorithm| key(algorithm, &[]));
+        for key in &keys {
+            memo.insert(key.clone(), "cigar");
+        }
+        assert!(!memo.guesses.contains_key(&keys[0]));
+        assert!(memo.guesses.contains_key(&keys[2]));
+        assert_eq!(memo.order.len(), 2);
+        memo.capacity = 0;
+        memo.shrink();
+        memo.insert(keys[0].clone(), "cigar");
+        assert!(memo.guesses.is_empty());
+
+        let stats = Stats { hits: 3, misses: 1 };
+        assert_eq!(stats.hit_rate(), 0.75);
+        assert_eq!(
+            stats.since(Stats { hits: 1, misses: 1 }),
+            Stats { hits: 2, misses: 0 }
+        );
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+    }
+}
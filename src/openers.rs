@@ -0,0 +1,179 @@
+//! Rating every word as the first guess of a game.
+//!
+//! Before the first guess the answer could be any dictionary word, with the same frequency
+//! weights the guessers use, so an opener's scores can be worked out exactly by trying it
+//! against every one of them.
+
+use std::io;
+
+use crate::{Correctness, json, parallel, solver::entropy};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Opener {
+    pub word: &'static str,
+    /// Expected information, in bits.
+    pub bits: f64,
+    /// How many words are expected to be left after it, for an answer picked by frequency.
+    pub expected: f64,
+    /// The most words a single pattern leaves.
+    pub worst: usize,
+    /// Mean guesses over the solved games of a benchmark opening with it, when one was run.
+    ///
+    /// Failed games are left out of the mean rather than counted at some number of guesses,
+    /// so read it next to `failed`.
+    pub average: Option<f64>,
+    /// How many games of that benchmark were not solved.
+    pub failed: Option<usize>,
+}
+
+/// Rates each of `words` as the opener when the answer is one of `words`, weighted by their
+/// counts. The openers come back in the order of `words`.
+pub fn score(words: &[(&'static str, usize)], threads: usize) -> Vec<Opener> {
+    let total: usize = words.iter().map(|&(_, count)| count).sum();
+    parallel::map(words, threads, |&(probe, _)| {
+        // Words and their weight, by pattern.
+        let mut buckets = [(0usize, 0usize); 243];
+        for &(word, count) in words {
+            let pattern = Correctness::compute(word, probe);
+            let bucket = &mut buckets[pattern.iter().fold(0, |i, &c| i * 3 + c as usize)];
+            bucket.0 += 1;
+            bucket.1 += count;
+        }
+        Opener {
+            word: probe,
+            bits: entropy(buckets.iter().map(|&(_, weight)| weight), total),
+            expected: buckets
+                .iter()
+                .map(|&(n, weight)| n as f64 * weight as f64 / total as f64)
+                .sum(),
+            worst: buckets.iter().map(|&(n, _)| n).max().unwrap_or(0),
+            average: None,
+            failed: None,
+        }
+    })
+}
+
+pub fn write_text(openers: &[Opener], mut w: impl io::Write) -> io::Result<()> {
+    writeln!(
+        w,
+        "{:>4}  {:5}  {:>6}  {:>8}  {:>5}  {:>7}  {:>6}",
+        "rank", "word", "bits", "expected", "worst", "average", "failed"
+    )?;
+    for (rank, opener) in openers.iter().enumerate() {
+        let average = opener
+            .average
+            .map_or(String::new(), |average| format!("{average:7.3}"));
+        let failed = opener
+            .failed
+            .map_or(String::new(), |failed| format!("{failed:6}"));
+        writeln!(
+            w,
+            "{}",
+            format!(
+                "{:4}  {}  {:6.3}  {:8.1}  {:5}  {average:7}  {failed}",
+                rank + 1,
+                opener.word,
+                opener.bits,
+                opener.expected,
+                opener.worst
+            )
+            .trim_end()
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_json(openers: &[Opener], mut w: impl io::Write) -> io::Result<()> {
+    write!(w, "[")?;
+    for (i, opener) in openers.iter().enumerate() {
+        let sep = if i == 0 { "" } else { "," };
+        write!(
+            w,
+            "{sep}{{\"word\":\"{}\",\"bits\":{},\"expected\":{},\"worst\":{}",
+            opener.word,
+            json::number(opener.bits),
+            json::number(opener.expected),
+            opener.worst
+        )?;
+        if let Some(average) = opener.average {
+            write!(w, ",\"average\":{}", json::number(average))?;
+        }
+        if let Some(failed) = opener.failed {
+            write!(w, ",\"failed\":{failed}")?;
+        }
+        write!(w, "}}")?;
+    }
+    writeln!(w, "]")
+}
+
+/// Writes one `rank,word,bits,expected,worst,average,failed` row per opener, leaving
+/// `average` and `failed` empty where no benchmark was run.
+pub fn write_csv(openers: &[Opener], mut w: impl io::Write) -> io::Result<()> {
+    writeln!(w, "rank,word,bits,expected,worst,average,failed")?;
+    for (rank, opener) in openers.iter().enumerate() {
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{}",
+            rank + 1,
+            opener.word,
+            opener.bits,
+            opener.expected,
+            opener.worst,
+            opener
+                .average
+                .map_or(String::new(), |average| average.to_string()),
+            opener
+                .failed
+                .map_or(String::new(), |failed| failed.to_string())
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{score, write_csv, write_json};
+
+    #[test]
+    fn scores() {
+        let words = [("abcde", 2), ("fghij", 1), ("fghik", 1)];
+        let openers = score(&words, 2);
+        assert_eq!(
+            Vec::from_iter(openers.iter().map(|o| o.word)),
+            ["abcde", "fghij", "fghik"]
+        );
+        // abcde tells the other two apart from itself, but not from each other.
+        assert_eq!(openers[0].bits, 1.0);
+        assert_eq!(openers[0].expected, 1.5);
+        assert_eq!(openers[0].worst, 2);
+        // fghij tells all three apart.
+        assert_eq!(openers[1].bits, 1.5);
+        assert_eq!(openers[1].expected, 1.0);
+        assert_eq!(openers[1].worst, 1);
+        assert_eq!(
+            (openers[2].bits, openers[2].expected, openers[2].worst),
+            (1.5, 1.0, 1)
+        );
+    }
+
+    #[test]
+    fn writes() {
+        let mut openers = score(&[("abcde", 2), ("fghij", 1), ("fghik", 1)], 1);
+        openers[1].average = Some(2.5);
+        openers[1].failed = Some(1);
+        let mut json = Vec::new();
+        write_json(&openers[..2], &mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "[{\"word\":\"abcde\",\"bits\":1,\"expected\":1.5,\"worst\":2},\
+             {\"word\":\"fghij\",\"bits\":1.5,\"expected\":1,\"worst\":1,\"average\":2.5,\"failed\":1}]\n"
+        );
+        let mut csv = Vec::new();
+        write_csv(&openers[..2], &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "rank,word,bits,expected,worst,average,failed\n1,abcde,1,1.5,2,,\n2,fghij,1.5,1,1,2.5,1\n"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Scoped-thread helpers used to spread candidate scoring across cores.
+//!
+//! Work is split into contiguous chunks, one per worker, and results are
+//! merged back in input order, so the outcome never depends on how many
+//! threads were used.
+
+use std::thread;
+
+/// Applies `f` to every item on up to `threads` workers and returns the results in input order.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    map_chunks(items, threads, |chunk| chunk.iter().map(&f).collect())
+}
+
+/// Hands each worker one contiguous chunk of `items` and concatenates what they return.
+///
+/// Useful when scoring an item depends on state built up from the items before it in the
+/// same chunk; `f` must return one result per item.
+pub fn map_chunks<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&[T]) -> Vec<R> + Sync,
+{
+    let Some(chunk) = chunk_len(items.len(), threads) else {
+        return f(items);
+    };
+
+    thread::scope(|s| {
+        let workers: Vec<_> = items
+            .chunks(chunk)
+            .map(|chunk| s.spawn(|| f(chunk)))
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("scoring worker panicked"))
+            .collect()
+    })
+}
+
+/// Returns the index and score of the highest scoring item.
+///
+/// Ties go to the earliest item, exactly as a sequential `score > best` scan would.
+pub fn best<T, F>(items: &[T], threads: usize, score: F) -> Option<(usize, f64)>
+where
+    T: Sync,
+    F: Fn(&T) -> f64 + Sync,
+{
+    let Some(chunk) = chunk_len(items.len(), threads) else {
+        return scan(items.iter().map(score).enumerate());
+    };
+
+    thread::scope(|s| {
+        let workers: Vec<_> = items
+            .chunks(chunk)
+            .enumerate()
+            .map(|(n, items)| {
+                let score = &score;
+                s.spawn(move || {
+                    scan(
+                        items
+                            .iter()
+                            .enumerate()
+                            .map(|(i, item)| (n * chunk + i, score(item))),
+                    )
+                })
+            })
+            .collect();
+
+        scan(
+            workers
+                .into_iter()
+                .filter_map(|worker| worker.join().expect("scoring worker panicked")),
+        )
+    })
+}
+
+fn scan(scores: impl Iterator<Item = (usize, f64)>) -> Option<(usize, f64)> {
+    let mut best: Option<(usize, f64)> = None;
+    for (i, score) in scores {
+        match best {
+            Some((_, b)) if score <= b => {}
+            _ => best = Some((i, score)),
+        }
+    }
+    best
+}
+
+/// Chunk length for `len` items over `threads` workers, or `None` when one worker suffices.
+fn chunk_len(len: usize, threads: usize) -> Option<usize> {
+    let threads = threads.min(len);
+    (threads > 1).then(|| len.div_ceil(threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best, map};
+
+    #[test]
+    fn map_keeps_order() {
+        let items: Vec<usize> = (0..1000).collect();
+        for threads in [1, 2, 3, 7, 64, 5000] {
+            assert_eq!(
+                map(&items, threads, |&i| i * 2),
+                Vec::from_iter((0..1000).map(|i| i * 2))
+            );
+        }
+    }
+
+    #[test]
+    fn best_prefers_earliest_tie() {
+        let items = [1.0, 3.0, 2.0, 3.0, 0.5, 3.0, 1.0];
+        for threads in 1..=items.len() + 1 {
+            assert_eq!(best(&items, threads, |&s| s), Some((1, 3.0)));
+        }
+    }
+
+    #[test]
+    fn best_of_nothing() {
+        assert_eq!(best(&[] as &[f64], 4, |&s| s), None);
+    }
+}
@@ -0,0 +1,188 @@
+//! A line protocol for driving a guesser from other programs.
+//!
+//! Every line of input is one command, and gets one line of JSON back:
+//!
+//! ```text
+//! new                     {"ok":true,"game":2}
+//! suggest 2               {"ok":true,"guess":"tares","suggestions":[{"word":"rates","bits":5.86},...]}
+//! feedback tares WCWWW    {"ok":true,"remaining":401,"solved":false}
+//! candidates              {"ok":true,"count":401,"words":["aalii",...]}
+//! reset                   {"ok":true,"game":1}
+//! ```
+//!
+//! `new` starts the next game and `reset` starts over from the first. `suggest` gives the
+//! guesser's pick, and with a count also that many of the most informative words. Patterns
+//! are read like the interactive helper reads them. A command that fails answers
+//! `{"ok":false,"error":"..."}` and changes nothing. Blank lines are skipped and `quit` ends
+//! the session.
+
+use std::io::{self, BufRead, Write};
+
+use crate::{Correctness, Guesser, json, session::Session};
+
+/// Answers commands from `input` on `output` until `quit` or the end of `input`.
+pub fn serve<G, F>(
+    session: &mut Session<G, F>,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()>
+where
+    G: Guesser,
+    F: Fn() -> G,
+{
+    let mut game = 1;
+    for line in input.lines() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        let args = Vec::from_iter(words);
+        if command == "quit" {
+            break;
+        }
+        let response = respond(session, &mut game, command, &args)
+            .unwrap_or_else(|e| format!("{{\"ok\":false,\"error\":{}}}", json::string(&e)));
+        writeln!(output, "{response}")?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+fn respond<G, F>(
+    session: &mut Session<G, F>,
+    game: &mut usize,
+    command: &str,
+    args: &[&str],
+) -> Result<String, String>
+where
+    G: Guesser,
+    F: Fn() -> G,
+{
+    let expect_args = |n: usize| {
+        if args.len() > n {
+            Err(format!("{command} takes at most {n} arguments"))
+        } else {
+            Ok(())
+        }
+    };
+    match command {
+        "new" | "reset" => {
+            expect_args(0)?;
+            *game = if command == "new" { *game + 1 } else { 1 };
+            session.reset();
+            Ok(format!("{{\"ok\":true,\"game\":{game}}}"))
+        }
+        "suggest" => {
+            expect_args(1)?;
+            let k = match args.first() {
+                Some(k) => Some(
+                    k.parse::<usize>()
+                        .map_err(|_| format!("{k:?} is not a count"))?,
+                ),
+                None => None,
+            };
+            if session.is_solved() {
+                return Err("the game is already solved".to_string());
+            }
+            let guess = session.try_suggest().map_err(|e| e.to_string())?;
+            let mut response = format!("{{\"ok\":true,\"guess\":{}", json::string(guess));
+            if let Some(k) = k {
+                let suggestions =
+                    Vec::from_iter(session.candidates().suggestions(k).into_iter().map(
+                        |(word, bits)| {
+                            format!(
+                                "{{\"word\":{},\"bits\":{}}}",
+                                json::string(word),
+                                json::number(bits)
+                            )
+                        },
+                    ));
+                response += &format!(",\"suggestions\":[{}]", suggestions.join(","));
+            }
+            Ok(response + "}")
+        }
+        "feedback" => {
+            let [word, pattern @ ..] = args else {
+                return Err("usage: feedback WORD PATTERN".to_string());
+            };
+            let pattern = pattern.concat();
+            let mask = Correctness::parse_pattern(&pattern)
+                .ok_or_else(|| format!("{pattern:?} is not a pattern"))?;
+            let word = word.to_ascii_lowercase();
+            session
+                .feedback(&word, mask)
+                .map_err(|e| format!("{word}: {e}"))?;
+            Ok(format!(
+                "{{\"ok\":true,\"remaining\":{},\"solved\":{}}}",
+                session.candidates().len(),
+                session.is_solved()
+            ))
+        }
+        "candidates" => {
+            expect_args(0)?;
+            let candidates = session.candidates();
+            let words = Vec::from_iter(
+                candidates
+                    .remaining()
+                    .iter()
+                    .map(|&(word, _)| json::string(word)),
+            );
+            Ok(format!(
+                "{{\"ok\":true,\"count\":{},\"words\":[{}]}}",
+                candidates.len(),
+                words.join(",")
+            ))
+        }
+        _ => Err(format!("unknown command {command:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serve;
+    use crate::{Wordle, algorithms::cutoff::Cutoff, session::Session, solver::Solver};
+
+    fn run(input: &str) -> Vec<String> {
+        let mut session = Session::new(Wordle::new(), || Solver::new(Cutoff::new()));
+        let mut output = Vec::new();
+        serve(&mut session, input.as_bytes(), &mut output).unwrap();
+        Vec::from_iter(
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn game() {
+        let out = run(
+            "suggest\nfeedback tares WCWWW\n\nsuggest 2\nfeedback daily gggbb\ncandidates\nfeedback daiko 🟩🟩🟩🟩🟩\nsuggest\nquit\nsuggest\n",
+        );
+        assert_eq!(out.len(), 7);
+        assert_eq!(out[0], r#"{"ok":true,"guess":"tares"}"#);
+        assert_eq!(out[1], r#"{"ok":true,"remaining":401,"solved":false}"#);
+        assert!(out[2].starts_with(r#"{"ok":true,"guess":"daily","suggestions":[{"word":"#));
+        assert_eq!(out[4], r#"{"ok":true,"count":1,"words":["daiko"]}"#);
+        assert_eq!(out[5], r#"{"ok":true,"remaining":1,"solved":true}"#);
+        assert_eq!(
+            out[6],
+            r#"{"ok":false,"error":"the game is already solved"}"#
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let out = run("feedback tares\nfeedback zzzzz CCCCC\nsuggest many\nfly\nnew\nreset\n");
+        assert_eq!(out[0], r#"{"ok":false,"error":"\"\" is not a pattern"}"#);
+        assert_eq!(
+            out[1],
+            r#"{"ok":false,"error":"zzzzz: not in the dictionary"}"#
+        );
+        assert_eq!(out[2], r#"{"ok":false,"error":"\"many\" is not a count"}"#);
+        assert_eq!(out[3], r#"{"ok":false,"error":"unknown command \"fly\""}"#);
+        assert_eq!(out[4], r#"{"ok":true,"game":2}"#);
+        assert_eq!(out[5], r#"{"ok":true,"game":1}"#);
+    }
+}
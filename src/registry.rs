@@ -0,0 +1,302 @@
+//! The guessers a frontend can offer, looked up by name.
+//!
+//! [`Registry::builtin`] holds the algorithms this crate ships. A crate with guessers of its
+//! own can register them next to those and hand the registry to the same benchmarks and
+//! sessions the built-in ones run under.
+
+use std::fmt;
+
+use crate::{
+    Guesser,
+    algorithms::{
+        allocs::Allocs, cutoff::Cutoff, native::Native, precalc::Precalc, prune::Prune,
+        weight::Weight,
+    },
+    solver::{Scorer, Solver},
+};
+
+/// What a guesser can do beyond picking the next guess of a single game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Never plays a guess that hard mode rules forbid.
+    pub hard_mode: bool,
+    /// Its scores order every word it considers, not just the one it picks.
+    pub ranking: bool,
+    /// Can play several boards at once.
+    pub multi_board: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Algorithm {
+    /// What it is picked by on the command line and through the C API.
+    pub name: &'static str,
+    /// One line on how it guesses.
+    pub description: &'static str,
+    pub capabilities: Capabilities,
+    /// Builds a fresh guesser set up by `options`. Every game needs its own.
+    pub new: fn(options: &Options) -> Box<dyn Guesser>,
+}
+
+/// How a guesser from the registry is set up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Worker threads to score on. One unless set.
+    pub threads: usize,
+    /// The first guess, when not the algorithm's own.
+    pub opener: Option<String>,
+    /// Search the [`endgame`](crate::endgame) once at most this many words are left, or
+    /// never if zero.
+    pub endgame: usize,
+    /// Keep endgame searches to words that may still be the answer.
+    pub hard_mode: bool,
+}
+
+impl Options {
+    /// Sets up `solver` as these options ask.
+    pub fn apply<S: Scorer>(&self, solver: Solver<S>) -> Solver<S> {
+        let solver = solver
+            .with_threads(self.threads)
+            .with_endgame(self.endgame)
+            .with_hard_mode(self.hard_mode);
+        match &self.opener {
+            Some(opener) => solver.with_opener(opener.as_str()),
+            None => solver,
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            threads: 1,
+            opener: None,
+            endgame: 0,
+            hard_mode: false,
+        }
+    }
+}
+
+/// Every solver scores only words that are still possible, so all of them keep to hard mode.
+const SCORER: Capabilities = Capabilities {
+    hard_mode: true,
+    ranking: true,
+    multi_board: false,
+};
+
+/// The pruning scorers drop patterns as they go, so only the best score is exact.
+const PRUNING: Capabilities = Capabilities {
+    ranking: false,
+    ..SCORER
+};
+
+const BUILTIN: [Algorithm; 6] = [
+    Algorithm {
+        name: "native",
+        description: "Expected information, computing each word's pattern",
+        capabilities: SCORER,
+        new: |options| Box::new(options.apply(Solver::new(Native))),
+    },
+    Algorithm {
+        name: "allocs",
+        description: "Expected information, checking which patterns each word fits",
+        capabilities: SCORER,
+        new: |options| Box::new(options.apply(Solver::new(Allocs))),
+    },
+    Algorithm {
+        name: "precalc",
+        description: "Expected information, with matches of the 512 most frequent words cached",
+        capabilities: SCORER,
+        new: |options| Box::new(options.apply(Solver::new(Precalc))),
+    },
+    Algorithm {
+        name: "weight",
+        description: "Expected information plus the chance of being the answer",
+        capabilities: SCORER,
+        new: |options| Box::new(options.apply(Solver::new(Weight))),
+    },
+    Algorithm {
+        name: "prune",
+        description: "Like weight, skipping patterns no earlier word produced",
+        capabilities: PRUNING,
+        new: |options| Box::new(options.apply(Solver::new(Prune::new()))),
+    },
+    Algorithm {
+        name: "cutoff",
+        description: "Like prune, scoring only the most frequent third of the words",
+        capabilities: PRUNING,
+        new: |options| Box::new(options.apply(Solver::new(Cutoff::new()))),
+    },
+];
+
+/// Algorithms that came to play exactly like another, under the names they used to have.
+const BUILTIN_ALIASES: [(&str, &str); 2] = [("vexer", "allocs"), ("once", "allocs")];
+
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    algorithms: Vec<Algorithm>,
+    /// Other names for registered algorithms, each with the name it stands for.
+    aliases: Vec<(&'static str, &'static str)>,
+}
+
+impl Registry {
+    /// A registry with nothing in it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The algorithms this crate ships, in the order they were written.
+    pub fn builtin() -> Self {
+        Self {
+            algorithms: BUILTIN.to_vec(),
+            aliases: BUILTIN_ALIASES.to_vec(),
+        }
+    }
+
+    /// Adds `algorithm` after the ones already registered.
+    pub fn register(&mut self, algorithm: Algorithm) -> Result<(), RegistryError> {
+        self.check_name(algorithm.name)?;
+        self.algorithms.push(algorithm);
+        Ok(())
+    }
+
+    /// Lets the algorithm registered as `name` also be looked up as `alias`.
+    pub fn alias(&mut self, alias: &'static str, name: &str) -> Result<(), RegistryError> {
+        self.check_name(alias)?;
+        let name = self.get(name).ok_or(RegistryError::Unknown(alias))?.name;
+        self.aliases.push((alias, name));
+        Ok(())
+    }
+
+    fn check_name(&self, name: &'static str) -> Result<(), RegistryError> {
+        if self.get(name).is_some() {
+            return Err(RegistryError::Taken(name));
+        }
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(RegistryError::BadName(name));
+        }
+        Ok(())
+    }
+
+    /// The algorithm registered as `name`, or that `name` is an alias of.
+    pub fn get(&self, name: &str) -> Option<&Algorithm> {
+        let name = self
+            .aliases
+            .iter()
+            .find(|&&(alias, _)| alias == name)
+            .map_or(name, |&(_, name)| name);
+        self.algorithms.iter().find(|a| a.name == name)
+    }
+
+    /// Every algorithm, in the order it was registered.
+    pub fn iter(&self) -> impl Iterator<Item = &Algorithm> {
+        self.algorithms.iter()
+    }
+
+    /// Every alias with the name of the algorithm it stands for, in the order they were added.
+    pub fn aliases(&self) -> impl Iterator<Item = (&'static str, &'static str)> {
+        self.aliases.iter().copied()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryError {
+    /// Another algorithm already goes by this name.
+    Taken(&'static str),
+    /// The name is empty or has whitespace in it, which reports and baselines cannot hold.
+    BadName(&'static str),
+    /// The alias would stand for an algorithm that is not registered.
+    Unknown(&'static str),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Taken(name) => write!(f, "an algorithm named {name:?} is already registered"),
+            Self::BadName(name) => write!(f, "{name:?} cannot name an algorithm"),
+            Self::Unknown(alias) => write!(f, "{alias:?} stands for no registered algorithm"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Algorithm, Capabilities, Options, Registry, RegistryError};
+    use crate::{Guess, Guesser, Wordle};
+
+    #[test]
+    fn builtin() {
+        let registry = Registry::builtin();
+        let names = Vec::from_iter(registry.iter().map(|a| a.name));
+        assert_eq!(
+            names,
+            ["native", "allocs", "precalc", "weight", "prune", "cutoff"]
+        );
+        for alias in ["vexer", "once"] {
+            assert_eq!(registry.get(alias).unwrap().name, "allocs");
+        }
+        for algorithm in registry.iter() {
+            assert!(algorithm.capabilities.hard_mode);
+            let options = Options {
+                threads: 2,
+                opener: Some("cigar".to_string()),
+                ..Options::default()
+            };
+            assert_eq!((algorithm.new)(&options).guess(&[]), "cigar");
+        }
+        let cutoff = registry.get("cutoff").unwrap();
+        assert!(
+            Wordle::new()
+                .play("cigar", (cutoff.new)(&Options::default()))
+                .is_some()
+        );
+        assert!(registry.get("Cutoff").is_none());
+    }
+
+    #[test]
+    fn register() {
+        let mut registry = Registry::builtin();
+        let always = Algorithm {
+            name: "always-cigar",
+            description: "Guesses cigar, however the game is going",
+            capabilities: Capabilities::default(),
+            new: |_| Box::new(|_: &[Guess]| "cigar".to_string()),
+        };
+        assert_eq!(registry.register(always), Ok(()));
+        assert_eq!(registry.iter().last().unwrap().name, "always-cigar");
+        let guesser = (registry.get("always-cigar").unwrap().new)(&Options::default());
+        assert_eq!(Wordle::new().play("cigar", guesser), Some(1));
+
+        assert_eq!(
+            registry.register(always),
+            Err(RegistryError::Taken("always-cigar"))
+        );
+        assert_eq!(
+            registry.register(Algorithm {
+                name: "always cigar",
+                ..always
+            }),
+            Err(RegistryError::BadName("always cigar"))
+        );
+
+        assert_eq!(registry.alias("cigar", "always-cigar"), Ok(()));
+        assert_eq!(registry.get("cigar").unwrap().name, "always-cigar");
+        assert_eq!(
+            registry.alias("once", "native"),
+            Err(RegistryError::Taken("once"))
+        );
+        assert_eq!(
+            registry.register(Algorithm {
+                name: "vexer",
+                ..always
+            }),
+            Err(RegistryError::Taken("vexer"))
+        );
+        assert_eq!(
+            registry.alias("never", "never-cigar"),
+            Err(RegistryError::Unknown("never"))
+        );
+        assert_eq!(Registry::new().iter().count(), 0);
+    }
+}
@@ -0,0 +1,648 @@
+//! A small HTTP/1.1 service around one guesser.
+//!
+//! Every endpoint takes a JSON object by `POST` and answers with one:
+//!
+//! ```text
+//! /suggest     {"history":[{"word":"tares","pattern":"WCWWW"}],"k":3}
+//!              {"guess":"daily","remaining":401,"suggestions":[{"word":"...","bits":4.1},...]}
+//! /candidates  {"history":[...]}
+//!              {"count":401,"words":["aalii",...]}
+//! /score       {"answer":"cigar","guess":"tares"}
+//!              {"pattern":"WMWWW","emoji":"⬛🟨⬛⬛⬛"}
+//! /games       {} or {"number":196}
+//!              {"id":1,"guesses":0,"max":6}
+//! /games/1     {"guess":"tares"}
+//!              {"pattern":"WMWWW","guesses":1,"solved":false,"over":false}
+//! ```
+//!
+//! `k` is optional and leaves out `suggestions` when missing. A hosted game picks a random
+//! answer unless given a puzzle number, and tells the answer once it is over. At most
+//! [`MAX_GAMES`] games are kept going at once: starting another one drops the game that has
+//! waited longest for a guess. Failures answer `{"error":"..."}` with a 4xx status, or 502
+//! when the guesser fails.
+//!
+//! Requests are answered one at a time, which is what lets them all share one [`Session`]: a
+//! history that extends the previous request's only shows the guesser the new guesses, and
+//! asking again about the same history costs nothing. So that no client holds up the others
+//! for long, each one has five seconds to send its whole request and as long to take the
+//! answer, and the request line and headers are limited to 8 KiB.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Correctness, Guess, Guesser, daily,
+    json::{self, Value},
+    rng::Rng,
+    session::Session,
+};
+
+/// Guesses allowed in a hosted game.
+const MAX_GUESSES: usize = 6;
+/// The largest request body read, in bytes.
+const MAX_BODY: usize = 64 * 1024;
+/// The most bytes the request line and headers may take together.
+const MAX_HEAD: usize = 8 * 1024;
+/// How long a client may take to send its whole request, and to take the response.
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// How many hosted games are kept going at once.
+pub const MAX_GAMES: usize = 1024;
+/// How long to wait after failing to accept a connection, which tends to fail again at once
+/// while the process is out of file descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+pub struct Server<G, F> {
+    session: Session<G, F>,
+    games: HashMap<u64, Game>,
+    next_game: u64,
+    rng: Rng,
+    /// Counts requests to hosted games, to tell which one has been idle longest.
+    clock: u64,
+}
+
+struct Game {
+    answer: &'static str,
+    history: Vec<Guess<'static>>,
+    /// The [`Server::clock`] when the game was last played.
+    played: u64,
+}
+
+/// A response: the status code and a JSON body.
+pub type Response = (u16, String);
+
+impl<G, F> Server<G, F>
+where
+    G: Guesser,
+    F: Fn() -> G,
+{
+    /// A server advising through `session`, which picks hosted answers with `seed`.
+    pub fn new(session: Session<G, F>, seed: u64) -> Self {
+        Self {
+            session,
+            games: HashMap::new(),
+            next_game: 1,
+            rng: Rng::new(seed),
+            clock: 0,
+        }
+    }
+
+    /// Answers connections on `listener` for good, telling `failed` about every connection
+    /// that could not be accepted.
+    pub fn run(&mut self, listener: &TcpListener, mut failed: impl FnMut(io::Error)) -> ! {
+        loop {
+            match listener.accept() {
+                // One client going wrong is no reason to stop serving the others.
+                Ok((stream, _)) => {
+                    let _ = self.connection(stream);
+                }
+                Err(e) => {
+                    failed(e);
+                    std::thread::sleep(ACCEPT_BACKOFF);
+                }
+            }
+        }
+    }
+
+    fn connection(&mut self, stream: TcpStream) -> io::Result<()> {
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let mut reader = BufReader::new(Deadline {
+            stream: &stream,
+            deadline: Instant::now() + TIMEOUT,
+        });
+        let (status, body) = match read_request(&mut reader)? {
+            Ok((method, path, body)) => self.handle(&method, &path, &body),
+            Err(response) => response,
+        };
+        let mut stream = &stream;
+        write!(
+            stream,
+            "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            reason(status),
+            body.len()
+        )?;
+        stream.flush()
+    }
+
+    /// Answers one request.
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> Response {
+        let path = path.split('?').next().unwrap_or_default();
+        let game = path.strip_prefix("/games/");
+        if !matches!(path, "/suggest" | "/candidates" | "/score" | "/games") && game.is_none() {
+            return error(404, format!("no endpoint {path}"));
+        }
+        if method != "POST" {
+            return error(405, format!("{path} only answers POST"));
+        }
+        let body = if body.trim().is_empty() {
+            Value::Object(Vec::new())
+        } else {
+            match Value::parse(body) {
+                Ok(body @ Value::Object(_)) => body,
+                Ok(_) => return error(400, "the body must be a JSON object"),
+                Err(e) => return error(400, format!("the body is not JSON: {e}")),
+            }
+        };
+        let result = match (path, game) {
+            ("/suggest", _) => self.suggest(&body),
+            ("/candidates", _) => self.candidates(&body),
+            ("/score", _) => self.score(&body),
+            ("/games", _) => self.new_game(&body),
+            (_, Some(id)) => match id.parse() {
+                Ok(id) => self.play(id, &body),
+                Err(_) => Err((404, format!("no game {id}"))),
+            },
+            _ => unreachable!("checked above"),
+        };
+        match result {
+            Ok(body) => (200, body),
+            Err((status, message)) => error(status, message),
+        }
+    }
+
+    fn suggest(&mut self, body: &Value) -> Result<String, (u16, String)> {
+        let k = match body.get("k") {
+            None | Some(Value::Null) => None,
+            Some(k) => Some(
+                k.as_f64()
+                    .filter(|k| k.fract() == 0.0 && *k >= 0.0)
+                    .ok_or((400, "k must be a count".to_string()))? as usize,
+            ),
+        };
+        self.follow(body)?;
+        if self.session.is_solved() {
+            return Err((400, "the game is already solved".to_string()));
+        }
+        let guess = json::string(
+            self.session
+                .try_suggest()
+                .map_err(|e| (502, e.to_string()))?,
+        );
+        let mut response = format!(
+            "{{\"guess\":{guess},\"remaining\":{}",
+            self.session.candidates().len()
+        );
+        if let Some(k) = k {
+            let suggestions =
+                Vec::from_iter(self.session.candidates().suggestions(k).into_iter().map(
+                    |(word, bits)| {
+                        format!(
+                            "{{\"word\":{},\"bits\":{}}}",
+                            json::string(word),
+                            json::number(bits)
+                        )
+                    },
+                ));
+            response += &format!(",\"suggestions\":[{}]", suggestions.join(","));
+        }
+        Ok(response + "}")
+    }
+
+    fn candidates(&mut self, body: &Value) -> Result<String, (u16, String)> {
+        self.follow(body)?;
+        let candidates = self.session.candidates();
+        let words = Vec::from_iter(
+            candidates
+                .remaining()
+                .iter()
+                .map(|&(word, _)| json::string(word)),
+        );
+        Ok(format!(
+            "{{\"count\":{},\"words\":[{}]}}",
+            candidates.len(),
+            words.join(",")
+        ))
+    }
+
+    /// Brings the session to the `history` of `body`, keeping what it already has in common.
+    fn follow(&mut self, body: &Value) -> Result<(), (u16, String)> {
+        let history = match body.get("history") {
+            None => &[][..],
+            Some(history) => history
+                .as_array()
+                .ok_or((400, "history must be an array".to_string()))?,
+        };
+        let mut steps = Vec::with_capacity(history.len());
+        for (i, step) in history.iter().enumerate() {
+            let field = |name| step.get(name).and_then(Value::as_str);
+            let (Some(word), Some(pattern)) = (field("word"), field("pattern")) else {
+                return Err((400, format!("history[{i}] needs a word and a pattern")));
+            };
+            let mask = Correctness::parse_pattern(pattern)
+                .ok_or_else(|| (400, format!("history[{i}]: {pattern:?} is not a pattern")))?;
+            steps.push((word.to_ascii_lowercase(), mask));
+        }
+
+        let known = self.session.history();
+        let extends = known.len() <= steps.len()
+            && known
+                .iter()
+                .zip(&steps)
+                .all(|(guess, (word, mask))| guess.word == *word && guess.mask == *mask);
+        if !extends {
+            self.session.reset();
+        }
+        for (i, (word, mask)) in steps.iter().enumerate().skip(self.session.history().len()) {
+            self.session
+                .feedback(word, *mask)
+                .map_err(|e| (400, format!("history[{i}]: {word}: {e}")))?;
+        }
+        Ok(())
+    }
+
+    fn score(&self, body: &Value) -> Result<String, (u16, String)> {
+        let [answer, guess] = ["answer", "guess"].map(|name| {
+            let word = body
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_ascii_lowercase);
+            match word {
+                Some(word) if self.session.wordle().is_valid(&word) => Ok(word),
+                Some(word) => Err((400, format!("{word} is not in the dictionary"))),
+                None => Err((400, format!("{name} must be a word"))),
+            }
+        });
+        let pattern = Correctness::compute(&answer?, &guess?);
+        Ok(format!(
+            "{{\"pattern\":\"{}\",\"emoji\":\"{}\"}}",
+            String::from_iter(pattern.iter().map(|c| c.letter())),
+            String::from_iter(pattern.iter().map(|c| c.emoji()))
+        ))
+    }
+
+    fn new_game(&mut self, body: &Value) -> Result<String, (u16, String)> {
+        let answers = daily::answers();
+        let number = match body.get("number") {
+            None | Some(Value::Null) => self.rng.below(answers.len()),
+            Some(number) => number
+                .as_f64()
+                .filter(|n| n.fract() == 0.0 && (0.0..answers.len() as f64).contains(n))
+                .ok_or_else(|| {
+                    (
+                        400,
+                        format!("number must be a puzzle from 0 to {}", answers.len() - 1),
+                    )
+                })? as usize,
+        };
+        if self.games.len() >= MAX_GAMES {
+            let idle = self
+                .games
+                .iter()
+                .min_by_key(|(_, game)| game.played)
+                .map(|(&id, _)| id)
+                .expect("there are games");
+            self.games.remove(&idle);
+        }
+        let id = self.next_game;
+        self.next_game += 1;
+        self.clock += 1;
+        self.games.insert(
+            id,
+            Game {
+                answer: answers[number],
+                history: Vec::new(),
+                played: self.clock,
+            },
+        );
+        Ok(format!(
+            "{{\"id\":{id},\"guesses\":0,\"max\":{MAX_GUESSES}}}"
+        ))
+    }
+
+    fn play(&mut self, id: u64, body: &Value) -> Result<String, (u16, String)> {
+        let wordle = self.session.wordle();
+        let game = self
+            .games
+            .get_mut(&id)
+            .ok_or_else(|| (404, format!("no game {id}")))?;
+        let word = body
+            .get("guess")
+            .and_then(Value::as_str)
+            .ok_or((400, "guess must be a word".to_string()))?
+            .to_ascii_lowercase();
+        if !wordle.is_valid(&word) {
+            return Err((400, format!("{word} is not in the dictionary")));
+        }
+        if !wordle.allows(&game.history, &word) {
+            return Err((
+                400,
+                format!("{word} does not use every hint, as hard mode requires"),
+            ));
+        }
+        self.clock += 1;
+        game.played = self.clock;
+        let mask = Correctness::compute(game.answer, &word);
+        game.history.push(Guess {
+            word: word.into(),
+            mask,
+        });
+        let solved = mask == [Correctness::Correct; 5];
+        let over = solved || game.history.len() == MAX_GUESSES;
+        let mut response = format!(
+            "{{\"pattern\":\"{}\",\"guesses\":{},\"solved\":{solved},\"over\":{over}",
+            String::from_iter(mask.iter().map(|c| c.letter())),
+            game.history.len()
+        );
+        if over {
+            response += &format!(",\"answer\":{}", json::string(game.answer));
+            // Nothing more can happen in a finished game, so it need not be kept.
+            self.games.remove(&id);
+        }
+        Ok(response + "}")
+    }
+}
+
+/// Reads from a stream until a deadline, however slowly the bytes trickle in.
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Reads a request as its method, path and body, or the response that turns it down.
+fn read_request(
+    reader: &mut impl BufRead,
+) -> io::Result<Result<(String, String, String), Response>> {
+    let mut head = MAX_HEAD;
+    let mut line = String::new();
+    read_head_line(reader, &mut line, &mut head)?;
+    if !line.ends_with('\n') && head == 0 {
+        return Ok(Err(error(400, "the request line is too long")));
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Ok(Err(error(400, "malformed request line")));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        read_head_line(reader, &mut line, &mut head)?;
+        if !line.ends_with('\n') {
+            return Ok(Err(match head {
+                0 => error(431, format!("headers are limited to {MAX_HEAD} bytes")),
+                _ => error(400, "the headers were cut off"),
+            }));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            match value.trim().parse() {
+                Ok(n) => length = n,
+                Err(_) => return Ok(Err(error(400, "malformed Content-Length"))),
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return Ok(Err(error(
+            413,
+            format!("bodies are limited to {MAX_BODY} bytes"),
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    match String::from_utf8(body) {
+        Ok(body) => Ok(Ok((method, path, body))),
+        Err(_) => Ok(Err(error(400, "the body is not UTF-8"))),
+    }
+}
+
+/// Reads a line into `line`, out of the `head` bytes the request line and headers have left.
+/// A line that does not end in a newline ran out of them, or of input.
+fn read_head_line(
+    reader: &mut impl BufRead,
+    line: &mut String,
+    head: &mut usize,
+) -> io::Result<()> {
+    *head -= reader.take(*head as u64).read_line(line)?;
+    Ok(())
+}
+
+fn error(status: u16, message: impl AsRef<str>) -> Response {
+    (
+        status,
+        format!("{{\"error\":{}}}", json::string(message.as_ref())),
+    )
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Content Too Large",
+        431 => "Request Header Fields Too Large",
+        502 => "Bad Gateway",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_GAMES, Server, read_request};
+    use crate::{Wordle, algorithms::cutoff::Cutoff, session::Session, solver::Solver};
+
+    fn server() -> Server<Solver<Cutoff>, fn() -> Solver<Cutoff>> {
+        Server::new(
+            Session::new(Wordle::new(), || Solver::new(Cutoff::new())),
+            1,
+        )
+    }
+
+    #[test]
+    fn advice() {
+        let mut server = server();
+        let (status, body) = server.handle("POST", "/suggest", "");
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"guess":"tares","remaining":12947}"#);
+
+        let tares = r#"{"word":"tares","pattern":"WCWWW"}"#;
+        let (_, body) = server.handle(
+            "POST",
+            "/suggest",
+            &format!(r#"{{"history":[{tares}],"k":2}}"#),
+        );
+        assert!(body.starts_with(r#"{"guess":"daily","remaining":401,"suggestions":[{"word":"#));
+        let daily = r#"{"word":"daily","pattern":"gggbb"}"#;
+        let (status, body) = server.handle(
+            "POST",
+            "/candidates",
+            &format!(r#"{{"history":[{tares},{daily}]}}"#),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"count":1,"words":["daiko"]}"#);
+        // A different history starts over rather than building on the last one.
+        let (_, body) = server.handle(
+            "POST",
+            "/candidates",
+            r#"{"history":[{"word":"daiko","pattern":"CCCCC"}]}"#,
+        );
+        assert_eq!(body, r#"{"count":1,"words":["daiko"]}"#);
+
+        let (status, body) =
+            server.handle("POST", "/score", r#"{"answer":"cigar","guess":"TARES"}"#);
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"pattern":"WMMWW","emoji":"⬛🟨🟨⬛⬛"}"#);
+    }
+
+    #[test]
+    fn games() {
+        let mut server = server();
+        let (status, body) = server.handle("POST", "/games", r#"{"number":0}"#);
+        assert_eq!(
+            (status, body.as_str()),
+            (200, r#"{"id":1,"guesses":0,"max":6}"#)
+        );
+        let (_, body) = server.handle("POST", "/games/1", r#"{"guess":"tares"}"#);
+        assert_eq!(
+            body,
+            r#"{"pattern":"WMMWW","guesses":1,"solved":false,"over":false}"#
+        );
+        let (_, body) = server.handle("POST", "/games/1", r#"{"guess":"cigar"}"#);
+        assert_eq!(
+            body,
+            r#"{"pattern":"CCCCC","guesses":2,"solved":true,"over":true,"answer":"cigar"}"#
+        );
+        assert_eq!(
+            server.handle("POST", "/games/1", r#"{"guess":"cigar"}"#).0,
+            404
+        );
+        let (_, body) = server.handle("POST", "/games", "");
+        assert!(body.starts_with(r#"{"id":2,"#));
+    }
+
+    #[test]
+    fn idle_games() {
+        let mut server = server();
+        for _ in 0..MAX_GAMES {
+            server.handle("POST", "/games", "");
+        }
+        // Game 1 is played after game 2 started, so game 2 has waited longest.
+        assert_eq!(
+            server.handle("POST", "/games/1", r#"{"guess":"tares"}"#).0,
+            200
+        );
+        server.handle("POST", "/games", "");
+        assert_eq!(server.games.len(), MAX_GAMES);
+        assert_eq!(
+            server.handle("POST", "/games/2", r#"{"guess":"tares"}"#).0,
+            404
+        );
+        assert_eq!(
+            server.handle("POST", "/games/1", r#"{"guess":"cigar"}"#).0,
+            200
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut server = server();
+        for (method, path, body, status) in [
+            ("POST", "/nowhere", "", 404),
+            ("GET", "/suggest", "", 405),
+            ("POST", "/suggest", "[1]", 400),
+            ("POST", "/suggest", "{", 400),
+            ("POST", "/suggest", r#"{"k":-1}"#, 400),
+            ("POST", "/suggest", r#"{"history":[{"word":"tares"}]}"#, 400),
+            (
+                "POST",
+                "/suggest",
+                r#"{"history":[{"word":"zzzzz","pattern":"WWWWW"}]}"#,
+                400,
+            ),
+            (
+                "POST",
+                "/suggest",
+                r#"{"history":[{"word":"cigar","pattern":"CCCCC"}]}"#,
+                400,
+            ),
+            ("POST", "/score", r#"{"answer":"cigar"}"#, 400),
+            ("POST", "/games", r#"{"number":1e9}"#, 400),
+            ("POST", "/games/x", r#"{"guess":"tares"}"#, 404),
+        ] {
+            assert_eq!(
+                server.handle(method, path, body).0,
+                status,
+                "{method} {path} {body}"
+            );
+        }
+        let (_, body) = server.handle(
+            "POST",
+            "/suggest",
+            r#"{"history":[{"word":"zzzzz","pattern":"WWWWW"}]}"#,
+        );
+        assert_eq!(
+            body,
+            r#"{"error":"history[0]: zzzzz: not in the dictionary"}"#
+        );
+    }
+
+    #[test]
+    fn requests() {
+        let request = "POST /score HTTP/1.1\r\nHost: localhost\r\ncontent-length: 2\r\n\r\n{}";
+        assert_eq!(
+            read_request(&mut request.as_bytes()).unwrap(),
+            Ok(("POST".to_string(), "/score".to_string(), "{}".to_string()))
+        );
+        let request = "POST /score HTTP/1.1\r\nContent-Length: 999999\r\n\r\n";
+        assert_eq!(
+            read_request(&mut request.as_bytes())
+                .unwrap()
+                .unwrap_err()
+                .0,
+            413
+        );
+        assert_eq!(
+            read_request(&mut "\r\n".as_bytes()).unwrap().unwrap_err().0,
+            400
+        );
+
+        // An endless line is cut short instead of read into memory.
+        let long = "x".repeat(1 << 20);
+        let request = format!("POST /{long} HTTP/1.1\r\n\r\n");
+        assert_eq!(
+            read_request(&mut request.as_bytes())
+                .unwrap()
+                .unwrap_err()
+                .0,
+            400
+        );
+        let request = format!("POST /score HTTP/1.1\r\nCookie: {long}\r\n\r\n");
+        assert_eq!(
+            read_request(&mut request.as_bytes())
+                .unwrap()
+                .unwrap_err()
+                .0,
+            431
+        );
+        let many = "Accept: */*\r\n".repeat(1000);
+        let request = format!("POST /score HTTP/1.1\r\n{many}\r\n");
+        assert_eq!(
+            read_request(&mut request.as_bytes())
+                .unwrap()
+                .unwrap_err()
+                .0,
+            431
+        );
+    }
+}
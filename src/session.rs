@@ -0,0 +1,259 @@
+//! One game being played somewhere else, with a guesser advising on it.
+//!
+//! The frontends that help with a game in progress all need the same bookkeeping: the
+//! feedback so far, the words it leaves, and a guesser that has seen every step of it.
+
+use std::{borrow::Cow, fmt};
+
+use crate::{Correctness, Guess, GuessError, Guesser, Wordle, analysis::Candidates};
+
+pub struct Session<G, F> {
+    wordle: Wordle,
+    new_guesser: F,
+    guesser: G,
+    history: Vec<Guess<'static>>,
+    /// What was left before each guess, and after the last one.
+    candidates: Vec<Candidates>,
+    /// The longest stretch of `history` the guesser has been shown, if any.
+    shown: Option<usize>,
+    suggestion: Option<String>,
+}
+
+impl<G, F> Session<G, F>
+where
+    G: Guesser,
+    F: Fn() -> G,
+{
+    pub fn new(wordle: Wordle, new_guesser: F) -> Self {
+        Self {
+            wordle,
+            guesser: new_guesser(),
+            new_guesser,
+            history: Vec::new(),
+            candidates: vec![Candidates::new()],
+            shown: None,
+            suggestion: None,
+        }
+    }
+
+    pub fn wordle(&self) -> &Wordle {
+        &self.wordle
+    }
+
+    pub fn history(&self) -> &[Guess<'static>] {
+        &self.history
+    }
+
+    /// The words that fit every guess so far.
+    pub fn candidates(&self) -> &Candidates {
+        self.candidates
+            .last()
+            .expect("there is always a starting point")
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.history
+            .last()
+            .is_some_and(|g| g.mask == [Correctness::Correct; 5])
+    }
+
+    /// Starts over with a fresh guesser.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.candidates.truncate(1);
+        self.restart_guesser();
+    }
+
+    /// The guesser's pick for the next guess.
+    ///
+    /// Panics if the guesser fails; [`try_suggest`](Self::try_suggest) does not.
+    pub fn suggest(&mut self) -> &str {
+        match self.try_suggest() {
+            Ok(guess) => guess,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// The guesser's pick for the next guess, or why it could not make one. A guesser that
+    /// failed is replaced by a new one, which is shown the game from the start next time.
+    pub fn try_suggest(&mut self) -> Result<&str, GuessError> {
+        if self.suggestion.is_none() {
+            // Guessers only narrow their words by the last guess they are shown, so every step
+            // has to be shown in turn.
+            let from = self.shown.map_or(0, |n| n + 1);
+            let mut guess = String::new();
+            for n in from..=self.history.len() {
+                match self.guesser.try_guess(&self.history[..n]) {
+                    Ok(next) => guess = next,
+                    Err(e) => {
+                        self.restart_guesser();
+                        return Err(e);
+                    }
+                }
+            }
+            self.shown = Some(self.history.len());
+            self.suggestion = Some(guess);
+        }
+        Ok(self.suggestion.as_deref().expect("just suggested"))
+    }
+
+    /// Records that `word` was played and the game showed `mask`.
+    pub fn feedback(&mut self, word: &str, mask: [Correctness; 5]) -> Result<(), FeedbackError> {
+        if self.is_solved() {
+            return Err(FeedbackError::Solved);
+        }
+        if !self.wordle.is_valid(word) {
+            return Err(FeedbackError::NotAWord);
+        }
+        if !self.wordle.allows(&self.history, word) {
+            return Err(FeedbackError::HardMode);
+        }
+        let guess = Guess {
+            word: Cow::Owned(word.to_string()),
+            mask,
+        };
+        let mut next = self.candidates().clone();
+        next.apply(&guess);
+        if next.is_empty() {
+            return Err(FeedbackError::Contradiction);
+        }
+        self.history.push(guess);
+        self.candidates.push(next);
+        self.suggestion = None;
+        Ok(())
+    }
+
+    /// Takes back the last guess, returning whether there was one.
+    pub fn undo(&mut self) -> bool {
+        if self.history.pop().is_none() {
+            return false;
+        }
+        self.candidates.pop();
+        // The guesser cannot forget what it was shown, so a new one catches up on the rest.
+        self.restart_guesser();
+        true
+    }
+
+    fn restart_guesser(&mut self) {
+        self.guesser = (self.new_guesser)();
+        self.shown = None;
+        self.suggestion = None;
+    }
+}
+
+/// Why feedback was turned down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackError {
+    NotAWord,
+    /// The word does not use every hint so far, in a hard mode game.
+    HardMode,
+    /// No word fits the feedback together with what came before.
+    Contradiction,
+    Solved,
+}
+
+impl fmt::Display for FeedbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAWord => write!(f, "not in the dictionary"),
+            Self::HardMode => write!(f, "does not use every hint, as hard mode requires"),
+            Self::Contradiction => write!(f, "no word fits that pattern with the ones before"),
+            Self::Solved => write!(f, "the game is already solved"),
+        }
+    }
+}
+
+impl std::error::Error for FeedbackError {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::{FeedbackError, Session};
+    use crate::{
+        Correctness, Guess, GuessError, Guesser, Wordle, algorithms::cutoff::Cutoff, solver::Solver,
+    };
+
+    fn session() -> Session<Solver<Cutoff>, fn() -> Solver<Cutoff>> {
+        Session::new(Wordle::new(), || Solver::new(Cutoff::new()))
+    }
+
+    #[test]
+    fn feedback_and_undo() {
+        let mut session = session();
+        assert_eq!(session.suggest(), "tares");
+        session
+            .feedback("tares", Correctness::compute("cigar", "tares"))
+            .unwrap();
+        session
+            .feedback("lingo", Correctness::compute("cigar", "lingo"))
+            .unwrap();
+        // The guesser was not asked for a guess after `tares`, but must still narrow by it.
+        let suggestion = session.suggest().to_string();
+        assert!(
+            session
+                .candidates()
+                .remaining()
+                .iter()
+                .any(|&(word, _)| word == suggestion)
+        );
+
+        let left = session.candidates().len();
+        assert!(session.undo());
+        assert!(session.candidates().len() > left);
+        session
+            .feedback("cigar", [Correctness::Correct; 5])
+            .unwrap();
+        assert!(session.is_solved());
+        assert_eq!(
+            session.feedback("cigar", [Correctness::Correct; 5]),
+            Err(FeedbackError::Solved)
+        );
+        session.reset();
+        assert!(session.history().is_empty());
+        assert!(!session.undo());
+    }
+
+    #[test]
+    fn rejects() {
+        let mut session = session();
+        assert_eq!(
+            session.feedback("zzzzz", [Correctness::Wrong; 5]),
+            Err(FeedbackError::NotAWord)
+        );
+        session.feedback("cigar", [Correctness::Wrong; 5]).unwrap();
+        assert_eq!(
+            session.feedback("cigar", [Correctness::Correct; 5]),
+            Err(FeedbackError::Contradiction)
+        );
+    }
+
+    /// Fails every other guesser it builds.
+    struct Flaky(bool);
+
+    impl Guesser for Flaky {
+        fn guess(&mut self, _: &[Guess]) -> String {
+            "tares".to_string()
+        }
+
+        fn try_guess(&mut self, history: &[Guess]) -> Result<String, GuessError> {
+            match self.0 {
+                true => Err(GuessError("flaky".to_string())),
+                false => Ok(self.guess(history)),
+            }
+        }
+    }
+
+    #[test]
+    fn failing_guesser() {
+        let built = Cell::new(0);
+        let mut session = Session::new(Wordle::new(), || {
+            built.set(built.get() + 1);
+            Flaky(built.get() % 2 == 1)
+        });
+        assert_eq!(session.try_suggest(), Err(GuessError("flaky".to_string())));
+        // The failed guesser was replaced.
+        assert_eq!(session.try_suggest(), Ok("tares"));
+        assert_eq!(built.get(), 2);
+    }
+}
@@ -0,0 +1,354 @@
+//! The text the game offers to share once a puzzle is over.
+//!
+//! ```text
+//! Wordle 1,234 4/6*
+//!
+//! ⬛🟨⬛⬛⬛
+//! 🟨⬛🟩⬛⬛
+//! ⬛🟩🟩🟩⬛
+//! 🟩🟩🟩🟩🟩
+//! ```
+//!
+//! The header has the puzzle number, the guesses taken (`X` for a lost game) and a `*` for hard
+//! mode. The rows give away the patterns but not the words, which is the point of sharing them.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::{Correctness, analysis, daily, parallel};
+
+const SQUARES: [char; 6] = ['🟩', '🟨', '⬛', '⬜', '🟧', '🟦'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    /// The puzzle number, if the header was pasted too.
+    pub number: Option<usize>,
+    /// Guesses taken, or `None` for a lost game.
+    pub guesses: Option<usize>,
+    pub hard: bool,
+    pub rows: Vec<[Correctness; 5]>,
+}
+
+impl Share {
+    /// The answer of the puzzle the header names.
+    pub fn answer(&self) -> Option<&'static str> {
+        daily::answers().get(self.number?).copied()
+    }
+
+    /// Reads every share in `text`, such as a stretch of chat.
+    ///
+    /// A share starts at its header, or for bare grids at the first row after other text.
+    pub fn parse_all(text: &str) -> Result<Vec<Self>, ParseError> {
+        let lines = Vec::from_iter(text.lines());
+        let mut starts = Vec::new();
+        let mut after_row = false;
+        let mut after_header = false;
+        for (i, line) in lines.iter().enumerate() {
+            let (header, row) = (is_header(line), is_row(line));
+            if header || (row && !after_row && !after_header) {
+                starts.push(i);
+            }
+            after_row = row;
+            if !line.trim().is_empty() {
+                after_header = header;
+            }
+        }
+        if starts.is_empty() {
+            return text.parse().map(|share| vec![share]);
+        }
+
+        let mut shares = Vec::with_capacity(starts.len());
+        for (n, &start) in starts.iter().enumerate() {
+            let end = starts.get(n + 1).copied().unwrap_or(lines.len());
+            let share = lines[start..end]
+                .join("\n")
+                .parse()
+                .map_err(|e: ParseError| ParseError {
+                    line: e.line + start,
+                    ..e
+                })?;
+            shares.push(share);
+        }
+        Ok(shares)
+    }
+}
+
+/// Every dictionary word, with its frequency, that gets `pattern` against `answer`.
+pub fn producers(answer: &str, pattern: &[Correctness; 5]) -> Vec<(&'static str, usize)> {
+    Vec::from_iter(
+        analysis::words()
+            .iter()
+            .copied()
+            .filter(|(word, _)| Correctness::compute(answer, word) == *pattern),
+    )
+}
+
+/// How likely each of `answers` is to be the answer behind all of `shares`, most likely first.
+///
+/// Answers start out weighted by their dictionary frequency. Every row then weighs an answer
+/// by the share of dictionary words that get that row against it, as if players guessed at
+/// random, so answers no words could have led to drop out. Hard mode is not taken into account.
+pub fn posterior(
+    shares: &[Share],
+    answers: &[&'static str],
+    threads: usize,
+) -> Vec<(&'static str, f64)> {
+    let words = analysis::words();
+    let frequency: HashMap<_, _> = words.iter().copied().collect();
+    // The winning row says nothing: whatever the answer, the player ended up guessing it.
+    let rows = Vec::from_iter(
+        shares
+            .iter()
+            .flat_map(|share| &share.rows)
+            .filter(|&row| *row != [Correctness::Correct; 5]),
+    );
+
+    let scores = parallel::map(answers, threads, |&answer| {
+        let prior = *frequency.get(answer).filter(|&&count| count > 0)?;
+        let mut counts = [0usize; 243];
+        for &(word, _) in words {
+            counts[index(&Correctness::compute(answer, word))] += 1;
+        }
+        let mut score = (prior as f64).ln();
+        for row in &rows {
+            match counts[index(row)] {
+                0 => return None,
+                n => score += (n as f64 / words.len() as f64).ln(),
+            }
+        }
+        Some(score)
+    });
+
+    let best = scores
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut posterior = Vec::from_iter(
+        answers
+            .iter()
+            .zip(scores)
+            .filter_map(|(&answer, score)| Some((answer, (score? - best).exp()))),
+    );
+    let total: f64 = posterior.iter().map(|&(_, p)| p).sum();
+    for (_, p) in &mut posterior {
+        *p /= total;
+    }
+    posterior.sort_by(|a, b| b.1.total_cmp(&a.1));
+    posterior
+}
+
+/// A number in `0..243` for every pattern.
+fn index(pattern: &[Correctness; 5]) -> usize {
+    pattern.iter().fold(0, |i, &c| i * 3 + c as usize)
+}
+
+impl FromStr for Share {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut header = None;
+        let mut rows = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            let error = |reason| ParseError {
+                line: i + 1,
+                reason,
+            };
+            if is_header(line) {
+                if header.is_some() || !rows.is_empty() {
+                    return Err(error("expected one share, but found another header"));
+                }
+                header = Some(
+                    parse_header(line)
+                        .ok_or_else(|| error("expected a header like `Wordle 1,234 4/6`"))?,
+                );
+            } else if is_row(line) {
+                rows.push(
+                    Correctness::parse_pattern(line)
+                        .ok_or_else(|| error("a row needs five squares"))?,
+                );
+            }
+            // Anything else, like the link the game adds, is not part of the grid.
+        }
+
+        let line = s.lines().count();
+        let error = |reason| ParseError { line, reason };
+        if rows.is_empty() {
+            return Err(error("no rows of squares found"));
+        }
+        if rows.len() > 6 {
+            return Err(error("a game has at most six rows"));
+        }
+        let solved = rows.last() == Some(&[Correctness::Correct; 5]);
+        if rows[..rows.len() - 1].contains(&[Correctness::Correct; 5]) {
+            return Err(error("the game went on after it was solved"));
+        }
+        let (number, guesses, hard) = header.unwrap_or((None, solved.then_some(rows.len()), false));
+        if guesses != solved.then_some(rows.len()) {
+            return Err(error("the header's score does not match the rows"));
+        }
+        Ok(Self {
+            number,
+            guesses,
+            hard,
+            rows,
+        })
+    }
+}
+
+fn is_header(line: &str) -> bool {
+    line.split_whitespace()
+        .next()
+        .is_some_and(|word| word.eq_ignore_ascii_case("wordle"))
+}
+
+fn is_row(line: &str) -> bool {
+    !line.trim().is_empty()
+        && line
+            .chars()
+            .all(|c| c.is_whitespace() || SQUARES.contains(&c))
+}
+
+/// Reads `Wordle 1,234 4/6*` into the number, the guesses taken and hard mode.
+fn parse_header(line: &str) -> Option<(Option<usize>, Option<usize>, bool)> {
+    let mut number = None;
+    let mut score = None;
+    for token in line.split_whitespace().skip(1) {
+        if let Some((guesses, rest)) = token.split_once("/6") {
+            let guesses = match guesses {
+                "X" | "x" => None,
+                n => Some(n.parse().ok().filter(|n| (1..=6).contains(n))?),
+            };
+            score = Some((guesses, rest == "*"));
+        } else if number.is_none() {
+            // Thousands are separated by commas, or by dots in some languages.
+            let digits = String::from_iter(token.chars().filter(|c| !matches!(c, ',' | '.' | '#')));
+            number = digits.parse().ok();
+        }
+    }
+    let (guesses, hard) = score?;
+    Some((number, guesses, hard))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Share, posterior, producers};
+    use crate::Correctness;
+
+    #[test]
+    fn parse() {
+        let share: Share = "Wordle 1,234 4/6*\n\n⬛🟨⬛⬛⬛\n🟨⬛🟩⬛⬛\n⬛🟩🟩🟩⬛\n🟩🟩🟩🟩🟩\nhttps://www.nytimes.com/games/wordle"
+            .parse()
+            .unwrap();
+        assert_eq!(share.number, Some(1234));
+        assert_eq!(share.guesses, Some(4));
+        assert!(share.hard);
+        assert_eq!(share.rows.len(), 4);
+        assert_eq!(share.rows[1], mask![M W C W W]);
+
+        let lost: Share =
+            "Wordle 0 X/6\n⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛"
+                .parse()
+                .unwrap();
+        assert_eq!(lost.guesses, None);
+        assert_eq!(lost.answer(), Some("cigar"));
+
+        let bare: Share = "🟨⬛⬛⬛⬛\n🟧🟧🟧🟧🟧".parse().unwrap();
+        assert_eq!(
+            (bare.number, bare.guesses, bare.hard),
+            (None, Some(2), false)
+        );
+    }
+
+    #[test]
+    fn bad_shares() {
+        assert!(
+            "Wordle 1 3/6\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩"
+                .parse::<Share>()
+                .is_err()
+        );
+        assert!(
+            "Wordle 1 2/6\n⬛⬛⬛⬛\n🟩🟩🟩🟩🟩"
+                .parse::<Share>()
+                .is_err()
+        );
+        assert!("Wordle 1 7/6\n🟩🟩🟩🟩🟩".parse::<Share>().is_err());
+        assert!("Wordle 1 1/6".parse::<Share>().is_err());
+        assert_eq!(
+            "🟩🟩🟩🟩🟩\n🟩🟩🟩🟩🟩"
+                .parse::<Share>()
+                .unwrap_err()
+                .to_string(),
+            "line 2: the game went on after it was solved"
+        );
+    }
+
+    #[test]
+    fn reverse() {
+        let solved = producers("cigar", &[Correctness::Correct; 5]);
+        assert_eq!(Vec::from_iter(solved.iter().map(|&(w, _)| w)), ["cigar"]);
+        let row = producers("cigar", &mask![C C W W C]);
+        assert!(row.iter().any(|&(w, _)| w == "cider"));
+        assert!(
+            row.iter()
+                .all(|(w, _)| Correctness::compute("cigar", w) == mask![C C W W C])
+        );
+    }
+
+    #[test]
+    fn parse_all() {
+        let chat = "Ann:\nWordle 196 3/6\n\n⬛🟨⬛⬛⬛\n⬛🟩🟩🟩⬛\n🟩🟩🟩🟩🟩\nBob:\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩\n\n🟩🟩🟩🟩🟩";
+        let shares = Share::parse_all(chat).unwrap();
+        assert_eq!(shares.len(), 3);
+        assert_eq!(shares[0].number, Some(196));
+        assert_eq!(shares[0].rows.len(), 3);
+        assert_eq!(shares[1].rows.len(), 2);
+        assert_eq!(shares[2].guesses, Some(1));
+
+        let error =
+            Share::parse_all("⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩\nWordle 1 9/6\n🟩🟩🟩🟩🟩").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: expected a header like `Wordle 1,234 4/6`"
+        );
+    }
+
+    #[test]
+    fn deduce() {
+        let grid = |words: &[&str]| Share {
+            number: None,
+            guesses: Some(words.len()),
+            hard: false,
+            rows: Vec::from_iter(words.iter().map(|w| Correctness::compute("cigar", w))),
+        };
+        let shares = [
+            grid(&["tares", "lingo", "cigar"]),
+            grid(&["crane", "cigar"]),
+            grid(&["pious", "cigar"]),
+        ];
+        let answers = ["rebut", "cigar", "sissy", "humph"];
+        let ranked = posterior(&shares, &answers, 2);
+        assert_eq!(ranked[0].0, "cigar");
+        assert!((ranked.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let impossible = Share {
+            rows: vec![mask![C C C C W], [Correctness::Correct; 5]],
+            ..grid(&[])
+        };
+        assert!(posterior(&[impossible], &["cigar"], 1).is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+//! The guesser every algorithm shares, and the [`Scorer`] trait they plug into it.
+//!
+//! A [`Solver`] keeps the words that are still possible, in the order its scorer lists them,
+//! narrows them by every guess it has not been shown before and plays the opener. For every
+//! other guess it asks its scorer to rate the probe words and plays the best one, the earliest
+//! among equals. Algorithms differ only in how they score, so they are measured against each other
+//! on the same footing.
+
+use std::{borrow::Cow, cmp::Reverse, sync::OnceLock};
+
+use crate::{Guess, Guesser, analysis, endgame, memo, parallel};
+
+static BY_FREQUENCY: OnceLock<Vec<(&'static str, usize)>> = OnceLock::new();
+
+/// Every dictionary word with its frequency count, most frequent first.
+pub fn by_frequency() -> &'static [(&'static str, usize)] {
+    BY_FREQUENCY.get_or_init(|| {
+        let mut words = analysis::words().to_vec();
+        words.sort_unstable_by_key(|&(_, count)| Reverse(count));
+        words
+    })
+}
+
+/// The Shannon entropy, in bits, of outcomes that happen `counts` times out of `total`.
+pub fn entropy(counts: impl IntoIterator<Item = usize>, total: usize) -> f64 {
+    counts
+        .into_iter()
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// How an algorithm rates a possible next guess.
+pub trait Scorer: Sync {
+    /// The first guess, played without scoring whenever the dictionary has it.
+    fn opener(&self) -> &'static str {
+        "tares"
+    }
+
+    /// Every word that may be the answer, in the order ties between them go by: the
+    /// dictionary's unless overridden.
+    fn words(&self) -> &'static [(&'static str, usize)] {
+        analysis::words()
+    }
+
+    /// The words worth scoring as the next guess: all of `remaining` unless overridden.
+    fn probes<'a>(&self, remaining: &'a [(&'static str, usize)]) -> &'a [(&'static str, usize)] {
+        remaining
+    }
+
+    /// How good `probe`, with its frequency count, is as the next guess when the answer is one
+    /// of `remaining`, whose counts add up to `total`. Higher is better.
+    fn score(
+        &self,
+        probe: (&'static str, usize),
+        remaining: &[(&'static str, usize)],
+        total: usize,
+    ) -> f64;
+
+    /// What the guess is remembered under in the [`memo`], along with the words left, so games
+    /// that get to the same words can share it. `None` keeps it out of the memo.
+    ///
+    /// Scorers with settings that change their scores add those to the key. Scorers that carry
+    /// something over from one guess to the next return `None`.
+    fn memo_key(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Borrowed(std::any::type_name::<Self>()))
+    }
+
+    /// Scores every one of `probes`, in order, on up to `threads` workers.
+    ///
+    /// Scorers that learn something from one probe to use on the next override this.
+    fn score_all(
+        &mut self,
+        probes: &[(&'static str, usize)],
+        remaining: &[(&'static str, usize)],
+        total: usize,
+        threads: usize,
+    ) -> Vec<f64> {
+        parallel::map(probes, threads, |&probe| {
+            self.score(probe, remaining, total)
+        })
+    }
+}
+
+pub struct Solver<S> {
+    scorer: S,
+    remaining: Cow<'static, [(&'static str, usize)]>,
+    threads: usize,
+    /// Played first instead of the scorer's own opener.
+    opener: Option<String>,
+    /// How many guesses of the game `remaining` has been narrowed by.
+    seen: usize,
+    /// With at most this many words left the [`endgame`] is searched instead of scored.
+    endgame: usize,
+    hard_mode: bool,
+}
+
+impl<S: Scorer> Solver<S> {
+    pub fn new(scorer: S) -> Self {
+        Self {
+            remaining: Cow::Borrowed(scorer.words()),
+            scorer,
+            threads: 1,
+            opener: None,
+            seen: 0,
+            endgame: 0,
+            hard_mode: false,
+        }
+    }
+
+    /// Opens with `opener` instead of the word the scorer picked.
+    pub fn with_opener(mut self, opener: impl Into<String>) -> Self {
+        self.opener = Some(opener.into());
+        self
+    }
+
+    /// Scores probes on `threads` worker threads.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Plays the endgame perfectly once at most `threshold` words are left, as long as the
+    /// answer can be found in time and the search finishes within its
+    /// [budget](endgame::BUDGET). Zero leaves it to the scorer.
+    pub fn with_endgame(mut self, threshold: usize) -> Self {
+        self.endgame = threshold.min(endgame::MAX_WORDS);
+        self
+    }
+
+    /// Keeps the endgame to words that may still be the answer.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// The words that fit every guess shown so far, in the scorer's order.
+    pub fn remaining(&self) -> &[(&'static str, usize)] {
+        &self.remaining
+    }
+}
+
+impl<S: Scorer> Guesser for Solver<S> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        for guess in history.iter().skip(self.seen) {
+            match self.remaining {
+                Cow::Borrowed(remaining) => {
+                    self.remaining = Cow::Owned(
+                        remaining
+                            .iter()
+                            .filter(|&&(word, _)| guess.matches(word))
+                            .copied()
+                            .collect(),
+                    );
+                }
+                Cow::Owned(ref mut owned) => {
+                    owned.retain(|&(word, _)| guess.matches(word));
+                }
+            }
+        }
+        self.seen = self.seen.max(history.len());
+        let opener = self.opener.as_deref().unwrap_or(self.scorer.opener());
+        if history.is_empty() && self.remaining.iter().any(|&(word, _)| word == opener) {
+            return opener.to_string();
+        }
+        assert!(
+            !self.remaining.is_empty(),
+            "no word fits every guess so far"
+        );
+
+        if self.remaining.len() <= self.endgame {
+            let turns = endgame::TURNS.saturating_sub(history.len());
+            if let Ok(plan) = endgame::solve(&self.remaining, turns, self.hard_mode) {
+                return plan.guess.to_string();
+            }
+        }
+
+        let key = self
+            .scorer
+            .memo_key()
+            .map(|algorithm| memo::Key::new(algorithm, &self.remaining));
+        if let Some(guess) = key.as_ref().and_then(memo::get) {
+            return guess;
+        }
+
+        let total: usize = self.remaining.iter().map(|&(_, count)| count).sum();
+        let probes = self.scorer.probes(&self.remaining);
+        let scores = self
+            .scorer
+            .score_all(probes, &self.remaining, total, self.threads);
+        assert_eq!(scores.len(), probes.len(), "one score per probe");
+
+        // Ties go to the earlier word.
+        let mut best = 0;
+        for (i, &score) in scores.iter().enumerate() {
+            if score > scores[best] {
+                best = i;
+            }
+        }
+        let guess = probes[best].0;
+        if let Some(key) = key {
+            memo::insert(key, guess);
+        }
+        guess.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scorer, Solver, by_frequency, entropy};
+    use crate::{Correctness, Guess, Guesser, Wordle, analysis};
+
+    /// Plays the word that leaves the fewest candidates in the worst case.
+    struct Minimax;
+
+    impl Scorer for Minimax {
+        fn opener(&self) -> &'static str {
+            "raise"
+        }
+
+        fn score(
+            &self,
+            (probe, _): (&'static str, usize),
+            remaining: &[(&'static str, usize)],
+            _: usize,
+        ) -> f64 {
+            let mut buckets = [0usize; 243];
+            for &(word, _) in remaining {
+                let pattern = Correctness::compute(word, probe);
+                buckets[pattern.iter().fold(0, |i, &c| i * 3 + c as usize)] += 1;
+            }
+            -(buckets.into_iter().max().unwrap_or(0) as f64)
+        }
+    }
+
+    #[test]
+    fn plays() {
+        let wordle = Wordle::new();
+        for answer in ["cigar", "rebut", "raise"] {
+            assert!(
+                wordle
+                    .play(answer, Solver::new(Minimax))
+                    .is_some_and(|n| n <= 6)
+            );
+        }
+
+        let mut solver = Solver::new(Minimax);
+        assert_eq!(solver.guess(&[]), "raise");
+        let history = [Guess {
+            word: "raise".into(),
+            mask: Correctness::compute("cigar", "raise"),
+        }];
+        let second = solver.guess(&history);
+        assert!(
+            solver
+                .remaining()
+                .iter()
+                .all(|&(word, _)| history[0].matches(word))
+        );
+        assert!(solver.remaining().len() < 100);
+
+        // Shown the game so far all at once, it narrows by every guess in it.
+        let mut late = Solver::new(Minimax);
+        assert_eq!(late.guess(&history), second);
+        assert_eq!(late.remaining(), solver.remaining());
+    }
+
+    /// Rates every word the same, so the first remaining one is played.
+    struct Indifferent;
+
+    impl Scorer for Indifferent {
+        fn score(&self, _: (&'static str, usize), _: &[(&'static str, usize)], _: usize) -> f64 {
+            0.0
+        }
+    }
+
+    #[test]
+    fn ties() {
+        let mut solver = Solver::new(Indifferent).with_threads(3);
+        assert_eq!(solver.guess(&[]), "tares");
+        assert_eq!(
+            Solver::new(Indifferent).with_opener("cigar").guess(&[]),
+            "cigar"
+        );
+        let history = [Guess {
+            word: "tares".into(),
+            mask: [Correctness::Wrong; 5],
+        }];
+        let first = analysis::words()
+            .iter()
+            .find(|&&(word, _)| history[0].matches(word))
+            .unwrap()
+            .0;
+        assert_eq!(solver.guess(&history), first);
+
+        // A scorer listing its words by frequency breaks ties toward the most frequent.
+        struct Frequent;
+
+        impl Scorer for Frequent {
+            fn words(&self) -> &'static [(&'static str, usize)] {
+                by_frequency()
+            }
+
+            fn score(
+                &self,
+                _: (&'static str, usize),
+                _: &[(&'static str, usize)],
+                _: usize,
+            ) -> f64 {
+                0.0
+            }
+        }
+
+        let most_frequent = by_frequency()
+            .iter()
+            .find(|&&(word, _)| history[0].matches(word))
+            .unwrap()
+            .0;
+        assert_ne!(most_frequent, first);
+        assert_eq!(Solver::new(Frequent).guess(&history), most_frequent);
+    }
+
+    #[test]
+    fn endgame() {
+        let history = ["tares", "light"].map(|word| Guess {
+            word: word.into(),
+            mask: Correctness::compute("night", word),
+        });
+        let mut solver = Solver::new(Indifferent).with_endgame(20);
+        let guess = solver.guess(&history);
+        let remaining = solver.remaining();
+        assert!((2..=20).contains(&remaining.len()));
+        assert_eq!(
+            guess,
+            crate::endgame::solve(remaining, 4, false).unwrap().guess
+        );
+
+        // Keeping to the words left cannot be sure of the answer in time, so the scorer decides.
+        let mut hard = Solver::new(Indifferent)
+            .with_endgame(20)
+            .with_hard_mode(true);
+        assert_eq!(hard.guess(&history), remaining[0].0);
+    }
+
+    #[test]
+    fn entropies() {
+        assert_eq!(entropy([4, 4], 8), 1.0);
+        assert_eq!(entropy([1, 1, 1, 1, 0], 4), 2.0);
+        assert_eq!(entropy([5], 5), 0.0);
+    }
+}